@@ -0,0 +1,2 @@
+//! C dynamic & static library exporting the `ktx::capi` functions, see `include/ktx.h`.
+pub use ktx::capi::*;
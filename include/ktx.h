@@ -0,0 +1,70 @@
+#ifndef KTX_H
+#define KTX_H
+
+/* Generated with cbindgen, do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Parsed KTX data, owning a copy of the input data.
+typedef struct KtxTexture KtxTexture;
+
+// KTX header values, as described in the KTX 1 specification.
+typedef struct KtxHeaderFields {
+  bool big_endian;
+  uint32_t gl_type;
+  uint32_t gl_type_size;
+  uint32_t gl_format;
+  uint32_t gl_internal_format;
+  uint32_t gl_base_internal_format;
+  uint32_t pixel_width;
+  uint32_t pixel_height;
+  uint32_t pixel_depth;
+  uint32_t array_elements;
+  uint32_t faces;
+  uint32_t mipmap_levels;
+  uint32_t bytes_of_key_value_data;
+} KtxHeaderFields;
+
+// Copies & parses complete KTX data. Returns null if the data does not start with a
+// valid KTX1 header.
+//
+// The returned texture must be freed with `ktx_free`.
+//
+// # Safety
+// `data` must be valid for reads of `len` bytes.
+struct KtxTexture *ktx_open_memory(const uint8_t *data, size_t len);
+
+// Frees a texture returned by `ktx_open_memory`.
+// Does nothing if `texture` is null.
+//
+// # Safety
+// `texture` must be null or returned by `ktx_open_memory` & not already freed.
+void ktx_free(struct KtxTexture *texture);
+
+// Returns the texture header values.
+//
+// # Safety
+// `texture` must be a valid, non-null, texture.
+struct KtxHeaderFields ktx_header(const struct KtxTexture *texture);
+
+// Returns the number of stored texture levels, `mipmap_levels` or `1` when
+// `mipmap_levels` is `0`.
+//
+// # Safety
+// `texture` must be a valid, non-null, texture.
+uint32_t ktx_level_count(const struct KtxTexture *texture);
+
+// Writes the pointer & length of texture level data, valid until the texture is freed.
+//
+// Returns `false`, leaving the outputs unwritten, if the level is invalid or missing
+// from the data.
+//
+// # Safety
+// `texture` must be a valid, non-null, texture. `data` & `len` must be valid for writes.
+bool ktx_level(const struct KtxTexture *texture, uint32_t level, const uint8_t **data, size_t *len);
+
+#endif  /* KTX_H */
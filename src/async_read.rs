@@ -0,0 +1,122 @@
+//! Asynchronous KTX reading using [futures](https://docs.rs/futures) `AsyncRead`.
+//!
+//! Requires the `async` feature.
+use crate::{
+    format::{level_data_len, padding_len},
+    header::*,
+    read::MAX_PREALLOCATE,
+};
+use futures_util::{
+    io::{self, AsyncRead, AsyncReadExt},
+    stream::{self, Stream},
+};
+use std::fmt;
+
+/// Asynchronous KTX texture storage format reader.
+/// Provides [`KtxInfo`](../header/trait.KtxInfo.html).
+///
+/// # Example
+/// ```
+/// # futures_executor::block_on(async {
+/// use futures_util::StreamExt;
+/// use ktx::{AsyncDecoder, KtxInfo};
+///
+/// # let data: &[u8] = include_bytes!("../tests/babg-bc3.ktx");
+/// let decoder = AsyncDecoder::new(data).await?;
+/// assert_eq!(decoder.pixel_width(), 260);
+///
+/// let mut levels = std::pin::pin!(decoder.into_level_stream());
+/// while let Some(level) = levels.next().await {
+///     let level: Vec<u8> = level?;
+/// }
+/// # Ok::<(), std::io::Error>(()) }).unwrap();
+/// ```
+#[derive(Clone, Copy)]
+pub struct AsyncDecoder<R> {
+    header: KtxHeader,
+    data: R,
+}
+
+impl<R> AsRef<KtxHeader> for AsyncDecoder<R> {
+    #[inline]
+    fn as_ref(&self) -> &KtxHeader {
+        &self.header
+    }
+}
+
+impl<R> fmt::Debug for AsyncDecoder<R> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("AsyncDecoder")
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl<R: AsyncRead + Unpin> AsyncDecoder<R> {
+    /// Reads KTX header data and returns an `AsyncDecoder`.
+    pub async fn new(mut data: R) -> io::Result<Self> {
+        let mut header_data = [0; 64];
+        data.read_exact(&mut header_data).await?;
+        let header = KtxHeader::try_from_bytes(&header_data)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(Self { header, data })
+    }
+
+    /// Consumes the `AsyncDecoder` to return a stream reading texture levels starting at
+    /// level 0. Each level is read as the stream is polled, so consumers apply backpressure.
+    ///
+    /// For cubemap textures each level will contain all 6 faces
+    /// in order: +X, -X, +Y, -Y, +Z, -Z.
+    ///
+    /// The stream ends after yielding an error.
+    pub fn into_level_stream(self) -> impl Stream<Item = io::Result<Vec<u8>>> {
+        let end_level = self.header.mipmap_levels().max(1);
+        stream::unfold(Some((self, 0)), move |state| async move {
+            let (mut decoder, level) = state?;
+            if level >= end_level {
+                return None;
+            }
+            match decoder.read_level(level).await {
+                Ok(data) => Some((Ok(data), Some((decoder, level + 1)))),
+                Err(err) => Some((Err(err), None)),
+            }
+        })
+    }
+
+    /// Reads the next level, skipping key-value data before level 0.
+    async fn read_level(&mut self, level: u32) -> io::Result<Vec<u8>> {
+        let header = self.header;
+        if level == 0 && header.bytes_of_key_value_data() != 0 {
+            let key_value_len = header.bytes_of_key_value_data() as _;
+            io::copy((&mut self.data).take(key_value_len), &mut io::sink()).await?;
+        }
+
+        let mut len = [0; 4];
+        self.data.read_exact(&mut len).await?;
+        let image_size = match header.big_endian() {
+            true => u32::from_be_bytes(len),
+            false => u32::from_le_bytes(len),
+        };
+        let level_len = level_data_len(image_size, &header);
+
+        // imageSize is unchecked, so limit preallocation
+        let mut data = Vec::with_capacity(level_len.min(MAX_PREALLOCATE) as usize);
+        (&mut self.data)
+            .take(level_len)
+            .read_to_end(&mut data)
+            .await?;
+        if data.len() as u64 != level_len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+
+        if level + 1 < header.mipmap_levels().max(1) {
+            // skip mipPadding
+            io::copy(
+                (&mut self.data).take(padding_len(level_len)),
+                &mut io::sink(),
+            )
+            .await?;
+        }
+        Ok(data)
+    }
+}
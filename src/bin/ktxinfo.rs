@@ -0,0 +1,73 @@
+//! Prints the header, key/value metadata, level table & validation results of a KTX file.
+//!
+//! Usage: `ktxinfo <file.ktx>`
+use ktx::{lint::Severity, Ktx};
+use std::{env, fs, process};
+
+fn main() {
+    let path = match env::args_os().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: ktxinfo <file.ktx>");
+            process::exit(2);
+        }
+    };
+    let data = fs::read(&path).unwrap_or_else(|err| {
+        eprintln!("{}: {err}", path.to_string_lossy());
+        process::exit(1);
+    });
+    let image = Ktx::try_new(&data[..]).unwrap_or_else(|err| {
+        eprintln!("{}: {err}", path.to_string_lossy());
+        process::exit(1);
+    });
+    let header = image.as_ref();
+
+    println!("{}: {header}", path.to_string_lossy());
+    println!("{header:#}");
+
+    println!("\nkey/values:");
+    for (key, value) in image.key_values() {
+        let text = value.strip_suffix(&[0]).unwrap_or(value);
+        match std::str::from_utf8(text) {
+            Ok(text) if !text.contains('\0') => println!("  {key}: {text}"),
+            _ => println!("  {key}: <{} bytes>", value.len()),
+        }
+    }
+
+    println!("\nlevels:");
+    println!(
+        "  {:>5} {:>10} {:>10}  dimensions",
+        "level", "offset", "size"
+    );
+    let mut truncated = false;
+    for (level, texture) in image.try_textures().enumerate() {
+        if let Err(err) = texture {
+            println!("  error: {err}");
+            truncated = true;
+            break;
+        }
+        let range = image.level_range(level as _).expect("present level");
+        let dimensions = header.level_dimensions_display(level as _);
+        println!(
+            "  {level:>5} {:>10} {:>10}  {dimensions}",
+            range.start,
+            range.len()
+        );
+    }
+
+    println!("\nvalidation:");
+    let diagnostics = ktx::lint(&image);
+    for diagnostic in &diagnostics {
+        let severity = match diagnostic.severity() {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+        };
+        println!("  {severity}: {diagnostic}");
+    }
+    if diagnostics.is_empty() && !truncated {
+        println!("  ok");
+    }
+    if truncated {
+        process::exit(1);
+    }
+}
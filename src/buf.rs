@@ -0,0 +1,147 @@
+//! KTX parsing of non-contiguous data using [bytes](https://docs.rs/bytes) `Buf`.
+//!
+//! Requires the `bytes` feature.
+use crate::{
+    error::{HeaderError, KtxError},
+    format::{level_data_len, padding_len},
+    header::*,
+    key_value::KeyValues,
+};
+use bytes::{Buf, Bytes};
+use std::{convert::TryFrom, fmt};
+
+/// KTX texture storage format parser for `bytes::Buf` data, e.g. a chain of `Bytes` chunks
+/// received from the network. Provides [`KtxInfo`](../header/trait.KtxInfo.html).
+///
+/// Key/value & texture level data are yielded as `Bytes` using `Buf::copy_to_bytes`, which
+/// does not copy data within a single `Bytes` chunk. Only data spanning chunks is copied.
+///
+/// # Example
+/// ```
+/// use bytes::{Buf, Bytes};
+/// use ktx::{buf::BufDecoder, KtxInfo};
+///
+/// let data = Bytes::from_static(include_bytes!("../tests/babg-bc3.ktx"));
+/// let (first, second) = (data.slice(..1000), data.slice(1000..));
+///
+/// let decoder = BufDecoder::new(first.chain(second))?;
+/// assert_eq!(decoder.pixel_width(), 260);
+/// let texture_levels: Vec<Bytes> = decoder.read_textures().collect::<Result<_, _>>()?;
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub struct BufDecoder<B> {
+    header: KtxHeader,
+    key_value_data: Bytes,
+    buf: B,
+}
+
+impl<B> AsRef<KtxHeader> for BufDecoder<B> {
+    #[inline]
+    fn as_ref(&self) -> &KtxHeader {
+        &self.header
+    }
+}
+
+impl<B> fmt::Debug for BufDecoder<B> {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("BufDecoder")
+            .field("header", &self.header)
+            .finish()
+    }
+}
+
+impl<B: Buf> BufDecoder<B> {
+    /// Parses the KTX header & key/value data, returning a `BufDecoder`.
+    pub fn new(mut buf: B) -> Result<Self, HeaderError> {
+        if buf.remaining() < 64 {
+            return Err(HeaderError::TooShort);
+        }
+        let mut header_data = [0; 64];
+        buf.copy_to_slice(&mut header_data);
+        let header = KtxHeader::try_from_bytes(&header_data)?;
+
+        let key_value_len = (header.bytes_of_key_value_data() as usize).min(buf.remaining());
+        let key_value_data = buf.copy_to_bytes(key_value_len);
+        Ok(Self {
+            header,
+            key_value_data,
+            buf,
+        })
+    }
+
+    /// Returns an iterator over the key/value metadata pairs.
+    #[inline]
+    pub fn key_values(&self) -> KeyValues<'_> {
+        KeyValues::new(&self.key_value_data, self.header.big_endian())
+    }
+
+    /// Consumes the `BufDecoder` to return an iterator over texture levels starting at level 0.
+    ///
+    /// For cubemap textures each level will contain all 6 faces
+    /// in order: +X, -X, +Y, -Y, +Z, -Z.
+    ///
+    /// Yields a [`KtxError`](../enum.KtxError.html) for the first level missing from the data,
+    /// after which iteration ends.
+    #[inline]
+    pub fn read_textures(self) -> BufTextures<B> {
+        BufTextures {
+            header: self.header,
+            buf: self.buf,
+            next_level: 0,
+            end_level: self.header.mipmap_levels().max(1),
+        }
+    }
+}
+
+/// Iterator over texture level data as `Bytes`.
+/// See [`BufDecoder::read_textures`](struct.BufDecoder.html#method.read_textures).
+#[derive(Debug)]
+pub struct BufTextures<B> {
+    header: KtxHeader,
+    buf: B,
+    next_level: u32,
+    end_level: u32,
+}
+
+impl<B: Buf> BufTextures<B> {
+    fn read_level(&mut self) -> Option<Bytes> {
+        if self.buf.remaining() < 4 {
+            return None;
+        }
+        let image_size = match self.header.big_endian() {
+            true => self.buf.get_u32(),
+            false => self.buf.get_u32_le(),
+        };
+        let level_len = usize::try_from(level_data_len(image_size, &self.header)).ok()?;
+        if self.buf.remaining() < level_len {
+            return None;
+        }
+        let level = self.buf.copy_to_bytes(level_len);
+
+        // skip mipPadding
+        let padding = (padding_len(level_len as u64) as usize).min(self.buf.remaining());
+        self.buf.advance(padding);
+        Some(level)
+    }
+}
+
+impl<B: Buf> Iterator for BufTextures<B> {
+    type Item = Result<Bytes, KtxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_level >= self.end_level {
+            return None;
+        }
+        let level = self.next_level;
+        self.next_level += 1;
+        match self.read_level() {
+            Some(data) => Some(Ok(data)),
+            None => {
+                self.next_level = self.end_level;
+                Some(Err(KtxError::TruncatedLevel { level }))
+            }
+        }
+    }
+}
+
+impl<B: Buf> std::iter::FusedIterator for BufTextures<B> {}
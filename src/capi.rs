@@ -0,0 +1,142 @@
+//! C API for engine integration.
+//!
+//! Requires the `capi` feature. Build the C dynamic & static libraries, `libktx_capi`,
+//! with `cargo build --release -p ktx-capi` & include `include/ktx.h`,
+//! generated with `cbindgen --config cbindgen.toml --output include/ktx.h`.
+//!
+//! Doc comments of exported items are copied into the header so are plain text,
+//! without rustdoc links.
+//!
+//! # Example
+//! ```c
+//! KtxTexture *texture = ktx_open_memory(data, data_len);
+//! if (texture) {
+//!     KtxHeaderFields header = ktx_header(texture);
+//!     for (uint32_t level = 0; level < ktx_level_count(texture); level++) {
+//!         const uint8_t *level_data;
+//!         size_t level_len;
+//!         if (ktx_level(texture, level, &level_data, &level_len)) {
+//!             // upload level_data
+//!         }
+//!     }
+//!     ktx_free(texture);
+//! }
+//! ```
+use crate::{header::KtxHeader, slice::Ktx, KtxInfo};
+use std::{ptr, slice};
+
+/// Parsed KTX data, owning a copy of the input data.
+pub struct KtxTexture(Ktx<Vec<u8>>);
+
+/// KTX header values, as described in the KTX 1 specification.
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct KtxHeaderFields {
+    pub big_endian: bool,
+    pub gl_type: u32,
+    pub gl_type_size: u32,
+    pub gl_format: u32,
+    pub gl_internal_format: u32,
+    pub gl_base_internal_format: u32,
+    pub pixel_width: u32,
+    pub pixel_height: u32,
+    pub pixel_depth: u32,
+    pub array_elements: u32,
+    pub faces: u32,
+    pub mipmap_levels: u32,
+    pub bytes_of_key_value_data: u32,
+}
+
+impl From<&KtxHeader> for KtxHeaderFields {
+    fn from(header: &KtxHeader) -> Self {
+        Self {
+            big_endian: header.big_endian(),
+            gl_type: header.gl_type(),
+            gl_type_size: header.gl_type_size(),
+            gl_format: header.gl_format(),
+            gl_internal_format: header.gl_internal_format(),
+            gl_base_internal_format: header.gl_base_internal_format(),
+            pixel_width: header.pixel_width(),
+            pixel_height: header.pixel_height(),
+            pixel_depth: header.pixel_depth(),
+            array_elements: header.array_elements(),
+            faces: header.faces(),
+            mipmap_levels: header.mipmap_levels(),
+            bytes_of_key_value_data: header.bytes_of_key_value_data(),
+        }
+    }
+}
+
+/// Copies & parses complete KTX data. Returns null if the data does not start with a
+/// valid KTX1 header.
+///
+/// The returned texture must be freed with `ktx_free`.
+///
+/// # Safety
+/// `data` must be valid for reads of `len` bytes.
+#[no_mangle]
+pub unsafe extern "C" fn ktx_open_memory(data: *const u8, len: usize) -> *mut KtxTexture {
+    if data.is_null() {
+        return ptr::null_mut();
+    }
+    let data = slice::from_raw_parts(data, len);
+    match Ktx::try_new(data.to_vec()) {
+        Ok(ktx) => Box::into_raw(Box::new(KtxTexture(ktx))),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Frees a texture returned by `ktx_open_memory`.
+/// Does nothing if `texture` is null.
+///
+/// # Safety
+/// `texture` must be null or returned by `ktx_open_memory` & not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn ktx_free(texture: *mut KtxTexture) {
+    if !texture.is_null() {
+        drop(Box::from_raw(texture));
+    }
+}
+
+/// Returns the texture header values.
+///
+/// # Safety
+/// `texture` must be a valid, non-null, texture.
+#[no_mangle]
+pub unsafe extern "C" fn ktx_header(texture: *const KtxTexture) -> KtxHeaderFields {
+    (*texture).0.as_ref().into()
+}
+
+/// Returns the number of stored texture levels, `mipmap_levels` or `1` when
+/// `mipmap_levels` is `0`.
+///
+/// # Safety
+/// `texture` must be a valid, non-null, texture.
+#[no_mangle]
+pub unsafe extern "C" fn ktx_level_count(texture: *const KtxTexture) -> u32 {
+    (*texture).0.mipmap_levels().max(1)
+}
+
+/// Writes the pointer & length of texture level data, valid until the texture is freed.
+///
+/// Returns `false`, leaving the outputs unwritten, if the level is invalid or missing
+/// from the data.
+///
+/// # Safety
+/// `texture` must be a valid, non-null, texture. `data` & `len` must be valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn ktx_level(
+    texture: *const KtxTexture,
+    level: u32,
+    data: *mut *const u8,
+    len: *mut usize,
+) -> bool {
+    match (*texture).0.try_textures().nth(level as _) {
+        Some(Ok(level_data)) => {
+            *data = level_data.as_ptr();
+            *len = level_data.len();
+            true
+        }
+        _ => false,
+    }
+}
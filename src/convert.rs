@@ -0,0 +1,48 @@
+//! Conversion between KTX & other texture container formats.
+mod astc;
+#[cfg(feature = "pvr")]
+mod pvr;
+
+pub use astc::from_astc;
+#[cfg(feature = "pvr")]
+pub use pvr::{from_pvr, to_pvr};
+
+use crate::write::BuildError;
+use std::{error::Error, fmt};
+
+/// Error converting texture data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ConvertError {
+    /// Input data is invalid or truncated.
+    InvalidInput(&'static str),
+    /// The pixel format has no known equivalent in the output container.
+    UnsupportedFormat,
+    /// Building the output KTX data failed.
+    Build(BuildError),
+}
+
+impl From<BuildError> for ConvertError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::Build(err)
+    }
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::InvalidInput(reason) => write!(f, "invalid input: {reason}"),
+            Self::UnsupportedFormat => f.write_str("unsupported pixel format"),
+            Self::Build(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for ConvertError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Build(err) => Some(err),
+            _ => None,
+        }
+    }
+}
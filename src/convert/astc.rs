@@ -0,0 +1,71 @@
+//! ARM `.astc` file conversion.
+use super::ConvertError;
+use crate::{
+    format::{self, InternalFormat},
+    write::KtxBuilder,
+};
+
+/// `.astc` file magic number.
+const ASTC_MAGIC: u32 = 0x5CA1_AB13;
+const ASTC_HEADER_LEN: usize = 16;
+
+/// Converts an `.astc` file, as written by ARM astcenc, into KTX data with the matching
+/// linear `COMPRESSED_RGBA_ASTC_*` internal format.
+///
+/// 3D ASTC block sizes are not supported.
+///
+/// # Example
+/// ```
+/// use ktx::{convert, format::COMPRESSED_RGBA_ASTC_8X8_KHR, Ktx, KtxInfo};
+///
+/// // 16x8 pixel texture with 8x8 blocks
+/// let mut astc = vec![0x13, 0xAB, 0xA1, 0x5C, 8, 8, 1, 16, 0, 0, 8, 0, 0, 1, 0, 0];
+/// astc.extend_from_slice(&[0; 2 * 16]);
+///
+/// let ktx_data = convert::from_astc(&astc)?;
+/// let ktx = Ktx::new(ktx_data.as_slice());
+/// assert_eq!(ktx.gl_internal_format(), COMPRESSED_RGBA_ASTC_8X8_KHR);
+/// assert_eq!(ktx.pixel_width(), 16);
+/// # Ok::<(), convert::ConvertError>(())
+/// ```
+pub fn from_astc(astc: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    if astc.len() < ASTC_HEADER_LEN {
+        return Err(ConvertError::InvalidInput(
+            "ASTC data too short for a header",
+        ));
+    }
+    if u32::from_le_bytes([astc[0], astc[1], astc[2], astc[3]]) != ASTC_MAGIC {
+        return Err(ConvertError::InvalidInput("not ASTC data"));
+    }
+    let (block_w, block_h, block_d) = (astc[4] as u32, astc[5] as u32, astc[6]);
+    let dim = |idx: usize| u32::from_le_bytes([astc[idx], astc[idx + 1], astc[idx + 2], 0]);
+    let (width, height, depth) = (dim(7), dim(10), dim(13));
+
+    if block_w == 0 || block_h == 0 || block_d == 0 {
+        return Err(ConvertError::InvalidInput(
+            "ASTC block dimensions must be non-zero",
+        ));
+    }
+    if block_d > 1 {
+        return Err(ConvertError::UnsupportedFormat);
+    }
+    let internal_format = (format::COMPRESSED_RGBA_ASTC_4X4_KHR
+        ..=format::COMPRESSED_RGBA_ASTC_12X12_KHR)
+        .find(|f| InternalFormat(*f).block_dimensions() == Some((block_w, block_h)))
+        .ok_or(ConvertError::UnsupportedFormat)?;
+
+    let builder = KtxBuilder::new()
+        .gl_internal_format(internal_format)
+        .gl_base_internal_format(format::RGBA)
+        .pixel_width(width)
+        .pixel_height(height)
+        .pixel_depth(if depth > 1 { depth } else { 0 });
+
+    let (rows, row_len) = format::image_rows(&builder, 0).ok_or(ConvertError::UnsupportedFormat)?;
+    let data = rows
+        .checked_mul(row_len)
+        .and_then(|len| astc.get(ASTC_HEADER_LEN..ASTC_HEADER_LEN.checked_add(len)?))
+        .ok_or(ConvertError::InvalidInput("ASTC block data truncated"))?;
+
+    Ok(builder.level(data).to_vec()?)
+}
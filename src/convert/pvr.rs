@@ -0,0 +1,279 @@
+//! PowerVR `.pvr` v3 container conversion.
+use super::ConvertError;
+use crate::{
+    format::{self, *},
+    header::KtxInfo,
+    slice::Ktx,
+    write::KtxBuilder,
+};
+
+/// PVR v3 version identifier, `"PVR\x03"` little endian.
+const PVR3_VERSION: u32 = 0x0352_5650;
+const PVR3_HEADER_LEN: usize = 52;
+
+/// PVR colour space values.
+const PVR_LINEAR: u32 = 0;
+const PVR_SRGB: u32 = 1;
+
+/// PVR channel type values.
+const PVR_UNSIGNED_BYTE_NORM: u32 = 0;
+const PVR_SIGNED_FLOAT: u32 = 12;
+const PVR_UNSIGNED_FLOAT: u32 = 13;
+
+/// PVR BC6 compressed pixel format, mapped to a signed or unsigned GL format by channel type.
+const PVR_BC6: u64 = 14;
+
+/// (PVR compressed pixel format, linear GL internal format, GL base internal format).
+///
+/// PVR has a single BC1 format, converted from PVR as RGB DXT1 as the first entry is used.
+const COMPRESSED: [(u64, u32, u32); 35] = [
+    (0, COMPRESSED_RGB_PVRTC_2BPPV1_IMG, RGB),
+    (1, COMPRESSED_RGBA_PVRTC_2BPPV1_IMG, RGBA),
+    (2, COMPRESSED_RGB_PVRTC_4BPPV1_IMG, RGB),
+    (3, COMPRESSED_RGBA_PVRTC_4BPPV1_IMG, RGBA),
+    (4, COMPRESSED_RGBA_PVRTC_2BPPV2_IMG, RGBA),
+    (5, COMPRESSED_RGBA_PVRTC_4BPPV2_IMG, RGBA),
+    (6, ETC1_RGB8_OES, RGB),
+    (7, COMPRESSED_RGB_S3TC_DXT1_EXT, RGB),
+    (7, COMPRESSED_RGBA_S3TC_DXT1_EXT, RGBA),
+    (9, COMPRESSED_RGBA_S3TC_DXT3_EXT, RGBA),
+    (11, COMPRESSED_RGBA_S3TC_DXT5_EXT, RGBA),
+    (12, COMPRESSED_RED_RGTC1, RED),
+    (13, COMPRESSED_RG_RGTC2, RG),
+    (PVR_BC6, COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT, RGB),
+    (PVR_BC6, COMPRESSED_RGB_BPTC_SIGNED_FLOAT, RGB),
+    (15, COMPRESSED_RGBA_BPTC_UNORM, RGBA),
+    (22, COMPRESSED_RGB8_ETC2, RGB),
+    (23, COMPRESSED_RGBA8_ETC2_EAC, RGBA),
+    (24, COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2, RGBA),
+    (25, COMPRESSED_R11_EAC, RED),
+    (26, COMPRESSED_RG11_EAC, RG),
+    (27, COMPRESSED_RGBA_ASTC_4X4_KHR, RGBA),
+    (28, COMPRESSED_RGBA_ASTC_5X4_KHR, RGBA),
+    (29, COMPRESSED_RGBA_ASTC_5X5_KHR, RGBA),
+    (30, COMPRESSED_RGBA_ASTC_6X5_KHR, RGBA),
+    (31, COMPRESSED_RGBA_ASTC_6X6_KHR, RGBA),
+    (32, COMPRESSED_RGBA_ASTC_8X5_KHR, RGBA),
+    (33, COMPRESSED_RGBA_ASTC_8X6_KHR, RGBA),
+    (34, COMPRESSED_RGBA_ASTC_8X8_KHR, RGBA),
+    (35, COMPRESSED_RGBA_ASTC_10X5_KHR, RGBA),
+    (36, COMPRESSED_RGBA_ASTC_10X6_KHR, RGBA),
+    (37, COMPRESSED_RGBA_ASTC_10X8_KHR, RGBA),
+    (38, COMPRESSED_RGBA_ASTC_10X10_KHR, RGBA),
+    (39, COMPRESSED_RGBA_ASTC_12X10_KHR, RGBA),
+    (40, COMPRESSED_RGBA_ASTC_12X12_KHR, RGBA),
+];
+
+/// (PVR channel names, bits per channel, glType, glFormat, linear glInternalFormat).
+///
+/// 8 bit formats have the unsigned byte normalised channel type, others signed float.
+const UNCOMPRESSED: [(&[u8; 4], u8, u32, u32, u32); 13] = [
+    (b"r\0\0\0", 8, UNSIGNED_BYTE, RED, R8),
+    (b"rg\0\0", 8, UNSIGNED_BYTE, RG, RG8),
+    (b"rgb\0", 8, UNSIGNED_BYTE, RGB, RGB8),
+    (b"rgba", 8, UNSIGNED_BYTE, RGBA, RGBA8),
+    (b"bgra", 8, UNSIGNED_BYTE, BGRA, RGBA8),
+    (b"r\0\0\0", 16, HALF_FLOAT, RED, R16F),
+    (b"rg\0\0", 16, HALF_FLOAT, RG, RG16F),
+    (b"rgb\0", 16, HALF_FLOAT, RGB, RGB16F),
+    (b"rgba", 16, HALF_FLOAT, RGBA, RGBA16F),
+    (b"r\0\0\0", 32, FLOAT, RED, R32F),
+    (b"rg\0\0", 32, FLOAT, RG, RG32F),
+    (b"rgb\0", 32, FLOAT, RGB, RGB32F),
+    (b"rgba", 32, FLOAT, RGBA, RGBA32F),
+];
+
+/// Returns the PVR (pixel format, channel type) of an uncompressed format,
+/// channel names are in the low 4 bytes & bits per channel in the high 4 bytes.
+fn uncompressed_pvr_format(channels: &[u8; 4], bits: u8) -> (u64, u32) {
+    let bits = channels.map(|c| if c == 0 { 0 } else { bits });
+    let pixel_format =
+        u32::from_le_bytes(*channels) as u64 | (u32::from_le_bytes(bits) as u64) << 32;
+    let channel_type = match bits[0] {
+        8 => PVR_UNSIGNED_BYTE_NORM,
+        _ => PVR_SIGNED_FLOAT,
+    };
+    (pixel_format, channel_type)
+}
+
+/// Converts PowerVR `.pvr` v3 data into KTX data.
+///
+/// Supports PVRTC, ETC, EAC, BC1-7 & ASTC compressed formats plus common 8 bit, half float &
+/// float uncompressed formats. PVR metadata is discarded.
+///
+/// Requires the `pvr` feature.
+pub fn from_pvr(pvr: &[u8]) -> Result<Vec<u8>, ConvertError> {
+    if pvr.len() < PVR3_HEADER_LEN {
+        return Err(ConvertError::InvalidInput(
+            "PVR data too short for a header",
+        ));
+    }
+    let val = |idx: usize| u32::from_le_bytes([pvr[idx], pvr[idx + 1], pvr[idx + 2], pvr[idx + 3]]);
+    if val(0) != PVR3_VERSION {
+        return Err(ConvertError::InvalidInput("not little endian PVR v3 data"));
+    }
+    let pixel_format = val(8) as u64 | (val(12) as u64) << 32;
+    let srgb = val(16) == PVR_SRGB;
+    let channel_type = val(20);
+    let (height, width, depth) = (val(24), val(28), val(32));
+    let (surfaces, faces, mipmaps) = (val(36), val(40), val(44));
+    let metadata_len = val(48) as usize;
+
+    let mut builder = KtxBuilder::new();
+    if pixel_format >> 32 == 0 {
+        let signed_bc6 = pixel_format == PVR_BC6 && channel_type == PVR_SIGNED_FLOAT;
+        let (_, internal, base) = COMPRESSED
+            .iter()
+            .filter(|(_, internal, _)| {
+                pixel_format != PVR_BC6
+                    || signed_bc6 == (*internal == COMPRESSED_RGB_BPTC_SIGNED_FLOAT)
+            })
+            .find(|(pvr_format, ..)| *pvr_format == pixel_format)
+            .ok_or(ConvertError::UnsupportedFormat)?;
+        builder = builder
+            .gl_internal_format(with_color_space(*internal, srgb))
+            .gl_base_internal_format(*base);
+    } else {
+        let (_, bits, gl_type, gl_format, internal) = UNCOMPRESSED
+            .iter()
+            .find(|(channels, bits, ..)| {
+                uncompressed_pvr_format(channels, *bits) == (pixel_format, channel_type)
+            })
+            .ok_or(ConvertError::UnsupportedFormat)?;
+        builder = builder
+            .gl_type(*gl_type)
+            .gl_type_size(*bits as u32 / 8)
+            .gl_format(*gl_format)
+            .gl_internal_format(with_color_space(*internal, srgb))
+            .gl_base_internal_format(match *gl_format {
+                BGRA => RGBA,
+                f => f,
+            });
+    }
+
+    builder = builder
+        .pixel_width(width)
+        .pixel_height(height)
+        .pixel_depth(if depth > 1 { depth } else { 0 })
+        .array_elements(if surfaces > 1 { surfaces } else { 0 })
+        .faces(faces.max(1))
+        .mipmap_levels(mipmaps.max(1));
+
+    let mut data = pvr
+        .get(PVR3_HEADER_LEN.saturating_add(metadata_len)..)
+        .ok_or(ConvertError::InvalidInput("PVR metadata truncated"))?;
+    let too_large = ConvertError::InvalidInput("PVR texture dimensions too large");
+    let images = faces
+        .max(1)
+        .checked_mul(surfaces.max(1))
+        .ok_or(too_large.clone())? as usize;
+    for level in 0..mipmaps.max(1) {
+        let (rows, row_len) =
+            format::image_rows(&builder, level).ok_or(ConvertError::UnsupportedFormat)?;
+        let level_len = rows
+            .checked_mul(row_len)
+            .and_then(|len| len.checked_mul(images))
+            .ok_or(too_large.clone())?;
+        if data.len() < level_len {
+            return Err(ConvertError::InvalidInput("PVR texture data truncated"));
+        }
+        let (level_data, rest) = data.split_at(level_len);
+        data = rest;
+
+        // level_len fits in the input so this cannot overflow
+        let mut ktx_level = Vec::with_capacity(rows * padded_len(row_len) * images);
+        for row in level_data.chunks_exact(row_len) {
+            ktx_level.extend_from_slice(row);
+            ktx_level.resize(ktx_level.len() + padded_len(row_len) - row_len, 0);
+        }
+        builder = builder.level(ktx_level);
+    }
+
+    Ok(builder.to_vec()?)
+}
+
+/// Converts KTX data into PowerVR `.pvr` v3 data.
+///
+/// Supports the same formats as [`from_pvr`](fn.from_pvr.html). KTX key-value data is discarded.
+///
+/// Requires the `pvr` feature.
+pub fn to_pvr<D>(ktx: &Ktx<D>) -> Result<Vec<u8>, ConvertError>
+where
+    D: AsRef<[u8]>,
+{
+    let linear_format = ktx
+        .internal_format()
+        .to_linear_variant()
+        .unwrap_or_else(|| ktx.internal_format())
+        .0;
+
+    let (pixel_format, channel_type) = if ktx.is_compressed() {
+        let (pvr_format, ..) = COMPRESSED
+            .iter()
+            .find(|(_, internal, _)| *internal == linear_format)
+            .ok_or(ConvertError::UnsupportedFormat)?;
+        let channel_type = match linear_format {
+            COMPRESSED_RGB_BPTC_SIGNED_FLOAT => PVR_SIGNED_FLOAT,
+            COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => PVR_UNSIGNED_FLOAT,
+            _ => PVR_UNSIGNED_BYTE_NORM,
+        };
+        (*pvr_format, channel_type)
+    } else {
+        let (channels, bits, ..) = UNCOMPRESSED
+            .iter()
+            .find(|(_, _, gl_type, gl_format, internal)| {
+                *gl_type == ktx.gl_type()
+                    && *gl_format == ktx.gl_format()
+                    && *internal == linear_format
+            })
+            .ok_or(ConvertError::UnsupportedFormat)?;
+        uncompressed_pvr_format(channels, *bits)
+    };
+
+    let header = [
+        PVR3_VERSION,
+        0,
+        pixel_format as u32,
+        (pixel_format >> 32) as u32,
+        if ktx.is_srgb() { PVR_SRGB } else { PVR_LINEAR },
+        channel_type,
+        ktx.pixel_height().max(1),
+        ktx.pixel_width().max(1),
+        ktx.pixel_depth().max(1),
+        ktx.array_elements().max(1),
+        ktx.faces().max(1),
+        ktx.mipmap_levels().max(1),
+        0,
+    ];
+    let mut pvr: Vec<u8> = header.iter().flat_map(|v| v.to_le_bytes()).collect();
+
+    let swap_size = match ktx.big_endian() {
+        true => ktx.gl_type_size() as usize,
+        false => 1,
+    };
+    for (level, data) in ktx.try_textures().enumerate() {
+        let data = data.map_err(|_| ConvertError::InvalidInput("KTX texture data truncated"))?;
+        let (_, row_len) =
+            format::image_rows(ktx, level as _).ok_or(ConvertError::UnsupportedFormat)?;
+        let level_start = pvr.len();
+        for row in data.chunks_exact(padded_len(row_len)) {
+            pvr.extend_from_slice(&row[..row_len]);
+        }
+        if swap_size > 1 {
+            for value in pvr[level_start..].chunks_exact_mut(swap_size) {
+                value.reverse();
+            }
+        }
+    }
+    Ok(pvr)
+}
+
+/// Returns the sRGB variant of a linear internal format if `srgb`.
+fn with_color_space(internal_format: u32, srgb: bool) -> u32 {
+    match srgb {
+        true => InternalFormat(internal_format)
+            .to_srgb_variant()
+            .map_or(internal_format, |f| f.0),
+        false => internal_format,
+    }
+}
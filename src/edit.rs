@@ -0,0 +1,196 @@
+//! Editing of owned KTX data.
+//!
+//! Each edit validates the result like [`KtxBuilder::to_vec`](../write/struct.KtxBuilder.html#method.to_vec)
+//! & rewrites the data, normalized to little endian.
+//!
+//! Edits are not in-place, every edit copies all of the level & key/value data into a
+//! builder then writes a new copy of the whole file. For many edits to large textures
+//! prefer a single [`patch_header`](fn.patch_header.html) or building the data with
+//! [`KtxBuilder`](../write/struct.KtxBuilder.html) directly.
+//!
+//! # Example
+//! ```
+//! use ktx::{edit, Ktx, KtxInfo};
+//!
+//! let mut image = ktx::include_ktx!("../tests/babg-bc3.ktx").into_owned();
+//! edit::set_key_value(&mut image, "KTXorientation", "S=r,T=d\0")?;
+//! edit::drop_levels_below(&mut image, 16)?;
+//! assert_eq!(image.mipmap_levels(), 5);
+//! # Ok::<(), edit::EditError>(())
+//! ```
+use crate::{
+    error::KtxError,
+    key_value::KeyValues,
+    slice::Ktx,
+    write::{BuildError, KtxBuilder},
+    KtxInfo,
+};
+use std::{error::Error, fmt};
+
+/// Replaces the data of a texture level, which may have a different size if the header is
+/// also patched, see [`patch_header`](fn.patch_header.html).
+///
+/// Errors with [`EditError::InvalidLevel`](enum.EditError.html#variant.InvalidLevel) if the
+/// level is >= the `mipmap_levels` value (or `1` when `mipmap_levels` is `0`).
+pub fn replace_level(
+    ktx: &mut Ktx<Vec<u8>>,
+    level: u32,
+    data: impl Into<Vec<u8>>,
+) -> Result<(), EditError> {
+    let levels = ktx.mipmap_levels().max(1);
+    if level >= levels {
+        return Err(EditError::InvalidLevel { level, levels });
+    }
+    let mut builder = to_builder(ktx)?;
+    builder.levels[level as usize] = data.into();
+    apply(ktx, builder)
+}
+
+/// Sets a key/value metadata pair, replacing any existing pairs with the same key.
+///
+/// String values should include a NUL terminator.
+pub fn set_key_value(
+    ktx: &mut Ktx<Vec<u8>>,
+    key: &str,
+    value: impl AsRef<[u8]>,
+) -> Result<(), EditError> {
+    let builder = without_key(to_builder(ktx)?, key);
+    apply(ktx, builder.key_value(key, value))
+}
+
+/// Removes all key/value metadata pairs with the input key, returning whether any were removed.
+pub fn remove_key_value(ktx: &mut Ktx<Vec<u8>>, key: &str) -> Result<bool, EditError> {
+    if ktx.key_value(key).is_none() {
+        return Ok(false);
+    }
+    let builder = without_key(to_builder(ktx)?, key);
+    apply(ktx, builder)?;
+    Ok(true)
+}
+
+/// Drops mipmap levels with all dimensions smaller than `min_dimension` pixels, always
+/// keeping level 0.
+pub fn drop_levels_below(ktx: &mut Ktx<Vec<u8>>, min_dimension: u32) -> Result<(), EditError> {
+    let mut builder = to_builder(ktx)?;
+    let keep = (1..builder.levels.len() as u32)
+        .take_while(|&level| {
+            let (w, h, d) = ktx.level_dimensions(level);
+            w.max(h).max(d) >= min_dimension
+        })
+        .count()
+        + 1;
+    if keep < builder.levels.len() {
+        builder.levels.truncate(keep);
+        builder = builder.mipmap_levels(keep as u32);
+    }
+    apply(ktx, builder)
+}
+
+/// Patches header values using the [`KtxBuilder`](../write/struct.KtxBuilder.html) setters,
+/// keeping the existing key/value & level data.
+///
+/// # Example
+/// ```
+/// use ktx::{edit, format::*, KtxInfo};
+///
+/// let mut image = ktx::include_ktx!("../tests/babg-bc3.ktx").into_owned();
+/// edit::patch_header(&mut image, |header| {
+///     header.gl_internal_format(COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT)
+/// })?;
+/// assert!(image.is_srgb());
+/// # Ok::<(), edit::EditError>(())
+/// ```
+pub fn patch_header(
+    ktx: &mut Ktx<Vec<u8>>,
+    patch: impl FnOnce(KtxBuilder) -> KtxBuilder,
+) -> Result<(), EditError> {
+    let builder = patch(to_builder(ktx)?);
+    apply(ktx, builder)
+}
+
+/// Returns a builder with the header, key/value & level data of the input, normalized to
+/// little endian.
+fn to_builder(ktx: &Ktx<Vec<u8>>) -> Result<KtxBuilder, EditError> {
+    ktx.check_complete()?;
+    if ktx.big_endian() {
+        let mut little = Vec::with_capacity(ktx.data().len());
+        ktx.write_little_endian_to(&mut little)
+            .expect("write to vec");
+        return to_builder(&Ktx::new(little));
+    }
+
+    let mut builder = KtxBuilder::new();
+    builder.header = *ktx.as_ref();
+    builder.levels = ktx.textures().map(<[u8]>::to_vec).collect();
+    let key_value_end = 64 + ktx.bytes_of_key_value_data() as usize;
+    builder.key_value_data = ktx.data()[64..key_value_end].to_vec();
+    Ok(builder)
+}
+
+/// Returns the builder with all key/value pairs with the input key removed.
+fn without_key(builder: KtxBuilder, key: &str) -> KtxBuilder {
+    let mut out = KtxBuilder {
+        key_value_data: Vec::new(),
+        ..builder.clone()
+    };
+    out.header.bytes_of_key_value_data = 0;
+    for (k, v) in KeyValues::new(&builder.key_value_data, false).filter(|(k, _)| *k != key) {
+        out = out.key_value(k, v);
+    }
+    out
+}
+
+fn apply(ktx: &mut Ktx<Vec<u8>>, builder: KtxBuilder) -> Result<(), EditError> {
+    *ktx = Ktx::new(builder.to_vec()?);
+    Ok(())
+}
+
+/// Error editing KTX data.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EditError {
+    /// The existing KTX data is invalid.
+    Ktx(KtxError),
+    /// The edited KTX data is inconsistent.
+    Build(BuildError),
+    /// The level does not exist, i.e. it is >= `levels`.
+    InvalidLevel { level: u32, levels: u32 },
+}
+
+impl From<KtxError> for EditError {
+    #[inline]
+    fn from(err: KtxError) -> Self {
+        Self::Ktx(err)
+    }
+}
+
+impl From<BuildError> for EditError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::Build(err)
+    }
+}
+
+impl fmt::Display for EditError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Ktx(err) => err.fmt(f),
+            Self::Build(err) => err.fmt(f),
+            Self::InvalidLevel { level, levels } => {
+                write!(
+                    f,
+                    "texture level {level} does not exist, of {levels} levels"
+                )
+            }
+        }
+    }
+}
+
+impl Error for EditError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Ktx(err) => Some(err),
+            Self::Build(err) => Some(err),
+            Self::InvalidLevel { .. } => None,
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use core::fmt;
+
+/// Error parsing KTX data.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KtxError {
+    /// Texture level data, or its `imageSize`, extends beyond the end of the KTX data.
+    TruncatedLevel { level: u32 },
+    /// `mipmap_levels` exceeds 32, the most levels possible for `u32` dimensions.
+    TooManyLevels { levels: u32 },
+    /// Key/value data extends beyond the end of the data, or contains a malformed pair.
+    InvalidKeyValues,
+    /// Texture level data length cannot be split into equally sized faces or array elements.
+    UnevenLevel { level: u32 },
+}
+
+impl fmt::Display for KtxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TruncatedLevel { level } => {
+                write!(
+                    f,
+                    "texture level {level} extends beyond the end of the data"
+                )
+            }
+            Self::TooManyLevels { levels } => {
+                write!(f, "{levels} texture levels exceeds the maximum of 32")
+            }
+            Self::InvalidKeyValues => f.write_str("key/value data is truncated or malformed"),
+            Self::UnevenLevel { level } => write!(
+                f,
+                "texture level {level} cannot be split into equal faces or array elements"
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for KtxError {}
+
+/// Error parsing a KTX header, see
+/// [`KtxHeader::try_from_prefix`](header/struct.KtxHeader.html#method.try_from_prefix).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum HeaderError {
+    /// The data is shorter than the 64 byte header.
+    TooShort,
+    /// The data does not start with the KTX1 file identifier.
+    InvalidIdentifier,
+    /// The header `endianness` value is neither `0x04030201` nor `0x01020304`.
+    InvalidEndianness,
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::TooShort => f.write_str("KTX data is too short for a header"),
+            Self::InvalidIdentifier => f.write_str("Not KTX1"),
+            Self::InvalidEndianness => f.write_str("Invalid KTX endianness"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for HeaderError {}
@@ -0,0 +1,710 @@
+//! OpenGL format constants & properties used to interpret KTX header values.
+
+// glType values
+pub const BYTE: u32 = 0x1400;
+pub const UNSIGNED_BYTE: u32 = 0x1401;
+pub const SHORT: u32 = 0x1402;
+pub const UNSIGNED_SHORT: u32 = 0x1403;
+pub const INT: u32 = 0x1404;
+pub const UNSIGNED_INT: u32 = 0x1405;
+pub const FLOAT: u32 = 0x1406;
+pub const HALF_FLOAT: u32 = 0x140B;
+pub const HALF_FLOAT_OES: u32 = 0x8D61;
+pub const UNSIGNED_BYTE_3_3_2: u32 = 0x8032;
+pub const UNSIGNED_BYTE_2_3_3_REV: u32 = 0x8362;
+pub const UNSIGNED_SHORT_5_6_5: u32 = 0x8363;
+pub const UNSIGNED_SHORT_5_6_5_REV: u32 = 0x8364;
+pub const UNSIGNED_SHORT_4_4_4_4: u32 = 0x8033;
+pub const UNSIGNED_SHORT_4_4_4_4_REV: u32 = 0x8365;
+pub const UNSIGNED_SHORT_5_5_5_1: u32 = 0x8034;
+pub const UNSIGNED_SHORT_1_5_5_5_REV: u32 = 0x8366;
+pub const UNSIGNED_INT_8_8_8_8: u32 = 0x8035;
+pub const UNSIGNED_INT_8_8_8_8_REV: u32 = 0x8367;
+pub const UNSIGNED_INT_10_10_10_2: u32 = 0x8036;
+pub const UNSIGNED_INT_2_10_10_10_REV: u32 = 0x8368;
+pub const UNSIGNED_INT_24_8: u32 = 0x84FA;
+pub const UNSIGNED_INT_10F_11F_11F_REV: u32 = 0x8C3B;
+pub const UNSIGNED_INT_5_9_9_9_REV: u32 = 0x8C3E;
+pub const FLOAT_32_UNSIGNED_INT_24_8_REV: u32 = 0x8DAD;
+
+// glFormat & glBaseInternalFormat values
+pub const STENCIL_INDEX: u32 = 0x1901;
+pub const DEPTH_COMPONENT: u32 = 0x1902;
+pub const RED: u32 = 0x1903;
+pub const GREEN: u32 = 0x1904;
+pub const BLUE: u32 = 0x1905;
+pub const ALPHA: u32 = 0x1906;
+pub const RGB: u32 = 0x1907;
+pub const RGBA: u32 = 0x1908;
+pub const LUMINANCE: u32 = 0x1909;
+pub const LUMINANCE_ALPHA: u32 = 0x190A;
+pub const BGR: u32 = 0x80E0;
+pub const BGRA: u32 = 0x80E1;
+pub const RG: u32 = 0x8227;
+pub const RG_INTEGER: u32 = 0x8228;
+pub const DEPTH_STENCIL: u32 = 0x84F9;
+pub const RED_INTEGER: u32 = 0x8D94;
+pub const GREEN_INTEGER: u32 = 0x8D95;
+pub const BLUE_INTEGER: u32 = 0x8D96;
+pub const ALPHA_INTEGER: u32 = 0x8D97;
+pub const RGB_INTEGER: u32 = 0x8D98;
+pub const RGBA_INTEGER: u32 = 0x8D99;
+pub const BGR_INTEGER: u32 = 0x8D9A;
+pub const BGRA_INTEGER: u32 = 0x8D9B;
+
+// uncompressed glInternalFormat values
+pub const RGB8: u32 = 0x8051;
+pub const RGBA8: u32 = 0x8058;
+pub const SRGB8: u32 = 0x8C41;
+pub const SRGB8_ALPHA8: u32 = 0x8C43;
+pub const R8: u32 = 0x8229;
+pub const RG8: u32 = 0x822B;
+pub const R16F: u32 = 0x822D;
+pub const R32F: u32 = 0x822E;
+pub const RG16F: u32 = 0x822F;
+pub const RG32F: u32 = 0x8230;
+pub const RGBA32F: u32 = 0x8814;
+pub const RGB32F: u32 = 0x8815;
+pub const RGBA16F: u32 = 0x881A;
+pub const RGB16F: u32 = 0x881B;
+
+// compressed glInternalFormat values
+pub const COMPRESSED_RGB_S3TC_DXT1_EXT: u32 = 0x83F0;
+pub const COMPRESSED_RGBA_S3TC_DXT1_EXT: u32 = 0x83F1;
+pub const COMPRESSED_RGBA_S3TC_DXT3_EXT: u32 = 0x83F2;
+pub const COMPRESSED_RGBA_S3TC_DXT5_EXT: u32 = 0x83F3;
+pub const COMPRESSED_SRGB_S3TC_DXT1_EXT: u32 = 0x8C4C;
+pub const COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT: u32 = 0x8C4D;
+pub const COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT: u32 = 0x8C4E;
+pub const COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT: u32 = 0x8C4F;
+pub const COMPRESSED_RED_RGTC1: u32 = 0x8DBB;
+pub const COMPRESSED_SIGNED_RED_RGTC1: u32 = 0x8DBC;
+pub const COMPRESSED_RG_RGTC2: u32 = 0x8DBD;
+pub const COMPRESSED_SIGNED_RG_RGTC2: u32 = 0x8DBE;
+pub const COMPRESSED_RGBA_BPTC_UNORM: u32 = 0x8E8C;
+pub const COMPRESSED_SRGB_ALPHA_BPTC_UNORM: u32 = 0x8E8D;
+pub const COMPRESSED_RGB_BPTC_SIGNED_FLOAT: u32 = 0x8E8E;
+pub const COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT: u32 = 0x8E8F;
+pub const ETC1_RGB8_OES: u32 = 0x8D64;
+pub const COMPRESSED_R11_EAC: u32 = 0x9270;
+pub const COMPRESSED_SIGNED_R11_EAC: u32 = 0x9271;
+pub const COMPRESSED_RG11_EAC: u32 = 0x9272;
+pub const COMPRESSED_SIGNED_RG11_EAC: u32 = 0x9273;
+pub const COMPRESSED_RGB8_ETC2: u32 = 0x9274;
+pub const COMPRESSED_SRGB8_ETC2: u32 = 0x9275;
+pub const COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2: u32 = 0x9276;
+pub const COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2: u32 = 0x9277;
+pub const COMPRESSED_RGBA8_ETC2_EAC: u32 = 0x9278;
+pub const COMPRESSED_SRGB8_ALPHA8_ETC2_EAC: u32 = 0x9279;
+pub const COMPRESSED_RGB_PVRTC_4BPPV1_IMG: u32 = 0x8C00;
+pub const COMPRESSED_RGB_PVRTC_2BPPV1_IMG: u32 = 0x8C01;
+pub const COMPRESSED_RGBA_PVRTC_4BPPV1_IMG: u32 = 0x8C02;
+pub const COMPRESSED_RGBA_PVRTC_2BPPV1_IMG: u32 = 0x8C03;
+pub const COMPRESSED_SRGB_PVRTC_2BPPV1_EXT: u32 = 0x8A54;
+pub const COMPRESSED_SRGB_PVRTC_4BPPV1_EXT: u32 = 0x8A55;
+pub const COMPRESSED_SRGB_ALPHA_PVRTC_2BPPV1_EXT: u32 = 0x8A56;
+pub const COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT: u32 = 0x8A57;
+pub const COMPRESSED_RGBA_PVRTC_2BPPV2_IMG: u32 = 0x9137;
+pub const COMPRESSED_RGBA_PVRTC_4BPPV2_IMG: u32 = 0x9138;
+pub const ATC_RGB_AMD: u32 = 0x8C92;
+pub const ATC_RGBA_EXPLICIT_ALPHA_AMD: u32 = 0x8C93;
+pub const ATC_RGBA_INTERPOLATED_ALPHA_AMD: u32 = 0x87EE;
+pub const PALETTE4_RGB8_OES: u32 = 0x8B90;
+pub const PALETTE4_RGBA8_OES: u32 = 0x8B91;
+pub const PALETTE4_R5_G6_B5_OES: u32 = 0x8B92;
+pub const PALETTE4_RGBA4_OES: u32 = 0x8B93;
+pub const PALETTE4_RGB5_A1_OES: u32 = 0x8B94;
+pub const PALETTE8_RGB8_OES: u32 = 0x8B95;
+pub const PALETTE8_RGBA8_OES: u32 = 0x8B96;
+pub const PALETTE8_R5_G6_B5_OES: u32 = 0x8B97;
+pub const PALETTE8_RGBA4_OES: u32 = 0x8B98;
+pub const PALETTE8_RGB5_A1_OES: u32 = 0x8B99;
+pub const COMPRESSED_RGBA_ASTC_4X4_KHR: u32 = 0x93B0;
+pub const COMPRESSED_RGBA_ASTC_5X4_KHR: u32 = 0x93B1;
+pub const COMPRESSED_RGBA_ASTC_5X5_KHR: u32 = 0x93B2;
+pub const COMPRESSED_RGBA_ASTC_6X5_KHR: u32 = 0x93B3;
+pub const COMPRESSED_RGBA_ASTC_6X6_KHR: u32 = 0x93B4;
+pub const COMPRESSED_RGBA_ASTC_8X5_KHR: u32 = 0x93B5;
+pub const COMPRESSED_RGBA_ASTC_8X6_KHR: u32 = 0x93B6;
+pub const COMPRESSED_RGBA_ASTC_8X8_KHR: u32 = 0x93B7;
+pub const COMPRESSED_RGBA_ASTC_10X5_KHR: u32 = 0x93B8;
+pub const COMPRESSED_RGBA_ASTC_10X6_KHR: u32 = 0x93B9;
+pub const COMPRESSED_RGBA_ASTC_10X8_KHR: u32 = 0x93BA;
+pub const COMPRESSED_RGBA_ASTC_10X10_KHR: u32 = 0x93BB;
+pub const COMPRESSED_RGBA_ASTC_12X10_KHR: u32 = 0x93BC;
+pub const COMPRESSED_RGBA_ASTC_12X12_KHR: u32 = 0x93BD;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR: u32 = 0x93D0;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_5X4_KHR: u32 = 0x93D1;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_5X5_KHR: u32 = 0x93D2;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_6X5_KHR: u32 = 0x93D3;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_6X6_KHR: u32 = 0x93D4;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_8X5_KHR: u32 = 0x93D5;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_8X6_KHR: u32 = 0x93D6;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_8X8_KHR: u32 = 0x93D7;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10X5_KHR: u32 = 0x93D8;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10X6_KHR: u32 = 0x93D9;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10X8_KHR: u32 = 0x93DA;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_10X10_KHR: u32 = 0x93DB;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_12X10_KHR: u32 = 0x93DC;
+pub const COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR: u32 = 0x93DD;
+
+/// Returns the constant name of a `glType` value.
+///
+/// # Example
+/// ```
+/// assert_eq!(ktx::format::type_name(0x1401), Some("UNSIGNED_BYTE"));
+/// ```
+pub fn type_name(gl_type: u32) -> Option<&'static str> {
+    Some(match gl_type {
+        BYTE => "BYTE",
+        UNSIGNED_BYTE => "UNSIGNED_BYTE",
+        SHORT => "SHORT",
+        UNSIGNED_SHORT => "UNSIGNED_SHORT",
+        INT => "INT",
+        UNSIGNED_INT => "UNSIGNED_INT",
+        FLOAT => "FLOAT",
+        HALF_FLOAT => "HALF_FLOAT",
+        HALF_FLOAT_OES => "HALF_FLOAT_OES",
+        UNSIGNED_BYTE_3_3_2 => "UNSIGNED_BYTE_3_3_2",
+        UNSIGNED_BYTE_2_3_3_REV => "UNSIGNED_BYTE_2_3_3_REV",
+        UNSIGNED_SHORT_5_6_5 => "UNSIGNED_SHORT_5_6_5",
+        UNSIGNED_SHORT_5_6_5_REV => "UNSIGNED_SHORT_5_6_5_REV",
+        UNSIGNED_SHORT_4_4_4_4 => "UNSIGNED_SHORT_4_4_4_4",
+        UNSIGNED_SHORT_4_4_4_4_REV => "UNSIGNED_SHORT_4_4_4_4_REV",
+        UNSIGNED_SHORT_5_5_5_1 => "UNSIGNED_SHORT_5_5_5_1",
+        UNSIGNED_SHORT_1_5_5_5_REV => "UNSIGNED_SHORT_1_5_5_5_REV",
+        UNSIGNED_INT_8_8_8_8 => "UNSIGNED_INT_8_8_8_8",
+        UNSIGNED_INT_8_8_8_8_REV => "UNSIGNED_INT_8_8_8_8_REV",
+        UNSIGNED_INT_10_10_10_2 => "UNSIGNED_INT_10_10_10_2",
+        UNSIGNED_INT_2_10_10_10_REV => "UNSIGNED_INT_2_10_10_10_REV",
+        UNSIGNED_INT_24_8 => "UNSIGNED_INT_24_8",
+        UNSIGNED_INT_10F_11F_11F_REV => "UNSIGNED_INT_10F_11F_11F_REV",
+        UNSIGNED_INT_5_9_9_9_REV => "UNSIGNED_INT_5_9_9_9_REV",
+        FLOAT_32_UNSIGNED_INT_24_8_REV => "FLOAT_32_UNSIGNED_INT_24_8_REV",
+        _ => return None,
+    })
+}
+
+/// Returns the constant name of a `glFormat` or `glBaseInternalFormat` value.
+///
+/// # Example
+/// ```
+/// assert_eq!(ktx::format::format_name(0x1908), Some("RGBA"));
+/// ```
+pub fn format_name(gl_format: u32) -> Option<&'static str> {
+    Some(match gl_format {
+        STENCIL_INDEX => "STENCIL_INDEX",
+        DEPTH_COMPONENT => "DEPTH_COMPONENT",
+        RED => "RED",
+        GREEN => "GREEN",
+        BLUE => "BLUE",
+        ALPHA => "ALPHA",
+        RGB => "RGB",
+        RGBA => "RGBA",
+        LUMINANCE => "LUMINANCE",
+        LUMINANCE_ALPHA => "LUMINANCE_ALPHA",
+        BGR => "BGR",
+        BGRA => "BGRA",
+        RG => "RG",
+        RG_INTEGER => "RG_INTEGER",
+        DEPTH_STENCIL => "DEPTH_STENCIL",
+        RED_INTEGER => "RED_INTEGER",
+        GREEN_INTEGER => "GREEN_INTEGER",
+        BLUE_INTEGER => "BLUE_INTEGER",
+        ALPHA_INTEGER => "ALPHA_INTEGER",
+        RGB_INTEGER => "RGB_INTEGER",
+        RGBA_INTEGER => "RGBA_INTEGER",
+        BGR_INTEGER => "BGR_INTEGER",
+        BGRA_INTEGER => "BGRA_INTEGER",
+        _ => return None,
+    })
+}
+
+/// Returns the constant name of a `glInternalFormat` value.
+/// Unsized internal formats, e.g. `RGBA`, return the [`format_name`](fn.format_name.html).
+///
+/// # Example
+/// ```
+/// use ktx::format::*;
+///
+/// assert_eq!(
+///     internal_format_name(COMPRESSED_RGBA_S3TC_DXT5_EXT),
+///     Some("COMPRESSED_RGBA_S3TC_DXT5_EXT")
+/// );
+/// ```
+pub fn internal_format_name(gl_internal_format: u32) -> Option<&'static str> {
+    Some(match gl_internal_format {
+        RGB8 => "RGB8",
+        RGBA8 => "RGBA8",
+        SRGB8 => "SRGB8",
+        SRGB8_ALPHA8 => "SRGB8_ALPHA8",
+        R8 => "R8",
+        RG8 => "RG8",
+        R16F => "R16F",
+        R32F => "R32F",
+        RG16F => "RG16F",
+        RG32F => "RG32F",
+        RGBA32F => "RGBA32F",
+        RGB32F => "RGB32F",
+        RGBA16F => "RGBA16F",
+        RGB16F => "RGB16F",
+        COMPRESSED_RGB_S3TC_DXT1_EXT => "COMPRESSED_RGB_S3TC_DXT1_EXT",
+        COMPRESSED_RGBA_S3TC_DXT1_EXT => "COMPRESSED_RGBA_S3TC_DXT1_EXT",
+        COMPRESSED_RGBA_S3TC_DXT3_EXT => "COMPRESSED_RGBA_S3TC_DXT3_EXT",
+        COMPRESSED_RGBA_S3TC_DXT5_EXT => "COMPRESSED_RGBA_S3TC_DXT5_EXT",
+        COMPRESSED_SRGB_S3TC_DXT1_EXT => "COMPRESSED_SRGB_S3TC_DXT1_EXT",
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT => "COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT",
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT => "COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT",
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT => "COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT",
+        COMPRESSED_RED_RGTC1 => "COMPRESSED_RED_RGTC1",
+        COMPRESSED_SIGNED_RED_RGTC1 => "COMPRESSED_SIGNED_RED_RGTC1",
+        COMPRESSED_RG_RGTC2 => "COMPRESSED_RG_RGTC2",
+        COMPRESSED_SIGNED_RG_RGTC2 => "COMPRESSED_SIGNED_RG_RGTC2",
+        COMPRESSED_RGBA_BPTC_UNORM => "COMPRESSED_RGBA_BPTC_UNORM",
+        COMPRESSED_SRGB_ALPHA_BPTC_UNORM => "COMPRESSED_SRGB_ALPHA_BPTC_UNORM",
+        COMPRESSED_RGB_BPTC_SIGNED_FLOAT => "COMPRESSED_RGB_BPTC_SIGNED_FLOAT",
+        COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => "COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT",
+        ETC1_RGB8_OES => "ETC1_RGB8_OES",
+        COMPRESSED_R11_EAC => "COMPRESSED_R11_EAC",
+        COMPRESSED_SIGNED_R11_EAC => "COMPRESSED_SIGNED_R11_EAC",
+        COMPRESSED_RG11_EAC => "COMPRESSED_RG11_EAC",
+        COMPRESSED_SIGNED_RG11_EAC => "COMPRESSED_SIGNED_RG11_EAC",
+        COMPRESSED_RGB8_ETC2 => "COMPRESSED_RGB8_ETC2",
+        COMPRESSED_SRGB8_ETC2 => "COMPRESSED_SRGB8_ETC2",
+        COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 => "COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2",
+        COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => "COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2",
+        COMPRESSED_RGBA8_ETC2_EAC => "COMPRESSED_RGBA8_ETC2_EAC",
+        COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => "COMPRESSED_SRGB8_ALPHA8_ETC2_EAC",
+        COMPRESSED_RGB_PVRTC_4BPPV1_IMG => "COMPRESSED_RGB_PVRTC_4BPPV1_IMG",
+        COMPRESSED_RGB_PVRTC_2BPPV1_IMG => "COMPRESSED_RGB_PVRTC_2BPPV1_IMG",
+        COMPRESSED_RGBA_PVRTC_4BPPV1_IMG => "COMPRESSED_RGBA_PVRTC_4BPPV1_IMG",
+        COMPRESSED_RGBA_PVRTC_2BPPV1_IMG => "COMPRESSED_RGBA_PVRTC_2BPPV1_IMG",
+        COMPRESSED_SRGB_PVRTC_2BPPV1_EXT => "COMPRESSED_SRGB_PVRTC_2BPPV1_EXT",
+        COMPRESSED_SRGB_PVRTC_4BPPV1_EXT => "COMPRESSED_SRGB_PVRTC_4BPPV1_EXT",
+        COMPRESSED_SRGB_ALPHA_PVRTC_2BPPV1_EXT => "COMPRESSED_SRGB_ALPHA_PVRTC_2BPPV1_EXT",
+        COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT => "COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT",
+        COMPRESSED_RGBA_PVRTC_2BPPV2_IMG => "COMPRESSED_RGBA_PVRTC_2BPPV2_IMG",
+        COMPRESSED_RGBA_PVRTC_4BPPV2_IMG => "COMPRESSED_RGBA_PVRTC_4BPPV2_IMG",
+        ATC_RGB_AMD => "ATC_RGB_AMD",
+        ATC_RGBA_EXPLICIT_ALPHA_AMD => "ATC_RGBA_EXPLICIT_ALPHA_AMD",
+        ATC_RGBA_INTERPOLATED_ALPHA_AMD => "ATC_RGBA_INTERPOLATED_ALPHA_AMD",
+        PALETTE4_RGB8_OES => "PALETTE4_RGB8_OES",
+        PALETTE4_RGBA8_OES => "PALETTE4_RGBA8_OES",
+        PALETTE4_R5_G6_B5_OES => "PALETTE4_R5_G6_B5_OES",
+        PALETTE4_RGBA4_OES => "PALETTE4_RGBA4_OES",
+        PALETTE4_RGB5_A1_OES => "PALETTE4_RGB5_A1_OES",
+        PALETTE8_RGB8_OES => "PALETTE8_RGB8_OES",
+        PALETTE8_RGBA8_OES => "PALETTE8_RGBA8_OES",
+        PALETTE8_R5_G6_B5_OES => "PALETTE8_R5_G6_B5_OES",
+        PALETTE8_RGBA4_OES => "PALETTE8_RGBA4_OES",
+        PALETTE8_RGB5_A1_OES => "PALETTE8_RGB5_A1_OES",
+        COMPRESSED_RGBA_ASTC_4X4_KHR => "COMPRESSED_RGBA_ASTC_4X4_KHR",
+        COMPRESSED_RGBA_ASTC_5X4_KHR => "COMPRESSED_RGBA_ASTC_5X4_KHR",
+        COMPRESSED_RGBA_ASTC_5X5_KHR => "COMPRESSED_RGBA_ASTC_5X5_KHR",
+        COMPRESSED_RGBA_ASTC_6X5_KHR => "COMPRESSED_RGBA_ASTC_6X5_KHR",
+        COMPRESSED_RGBA_ASTC_6X6_KHR => "COMPRESSED_RGBA_ASTC_6X6_KHR",
+        COMPRESSED_RGBA_ASTC_8X5_KHR => "COMPRESSED_RGBA_ASTC_8X5_KHR",
+        COMPRESSED_RGBA_ASTC_8X6_KHR => "COMPRESSED_RGBA_ASTC_8X6_KHR",
+        COMPRESSED_RGBA_ASTC_8X8_KHR => "COMPRESSED_RGBA_ASTC_8X8_KHR",
+        COMPRESSED_RGBA_ASTC_10X5_KHR => "COMPRESSED_RGBA_ASTC_10X5_KHR",
+        COMPRESSED_RGBA_ASTC_10X6_KHR => "COMPRESSED_RGBA_ASTC_10X6_KHR",
+        COMPRESSED_RGBA_ASTC_10X8_KHR => "COMPRESSED_RGBA_ASTC_10X8_KHR",
+        COMPRESSED_RGBA_ASTC_10X10_KHR => "COMPRESSED_RGBA_ASTC_10X10_KHR",
+        COMPRESSED_RGBA_ASTC_12X10_KHR => "COMPRESSED_RGBA_ASTC_12X10_KHR",
+        COMPRESSED_RGBA_ASTC_12X12_KHR => "COMPRESSED_RGBA_ASTC_12X12_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_5X4_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_5X4_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_5X5_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_5X5_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_6X5_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_6X5_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_6X6_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_6X6_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_8X5_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_8X5_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_8X6_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_8X6_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_8X8_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_8X8_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_10X5_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_10X5_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_10X6_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_10X6_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_10X8_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_10X8_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_10X10_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_10X10_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_12X10_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_12X10_KHR",
+        COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR => "COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR",
+        _ => return format_name(gl_internal_format),
+    })
+}
+
+/// ASTC block dimensions in the order of their internal format values.
+const ASTC_BLOCKS: [(u32, u32); 14] = [
+    (4, 4),
+    (5, 4),
+    (5, 5),
+    (6, 5),
+    (6, 6),
+    (8, 5),
+    (8, 6),
+    (8, 8),
+    (10, 5),
+    (10, 6),
+    (10, 8),
+    (10, 10),
+    (12, 10),
+    (12, 12),
+];
+
+/// (linear, sRGB) internal format pairs, excluding ASTC.
+const SRGB_PAIRS: [(u32, u32); 14] = [
+    (RGB8, SRGB8),
+    (RGBA8, SRGB8_ALPHA8),
+    (COMPRESSED_RGB_S3TC_DXT1_EXT, COMPRESSED_SRGB_S3TC_DXT1_EXT),
+    (
+        COMPRESSED_RGBA_S3TC_DXT1_EXT,
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT,
+    ),
+    (
+        COMPRESSED_RGBA_S3TC_DXT3_EXT,
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT,
+    ),
+    (
+        COMPRESSED_RGBA_S3TC_DXT5_EXT,
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT,
+    ),
+    (COMPRESSED_RGBA_BPTC_UNORM, COMPRESSED_SRGB_ALPHA_BPTC_UNORM),
+    (COMPRESSED_RGB8_ETC2, COMPRESSED_SRGB8_ETC2),
+    (
+        COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+        COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2,
+    ),
+    (COMPRESSED_RGBA8_ETC2_EAC, COMPRESSED_SRGB8_ALPHA8_ETC2_EAC),
+    (
+        COMPRESSED_RGB_PVRTC_2BPPV1_IMG,
+        COMPRESSED_SRGB_PVRTC_2BPPV1_EXT,
+    ),
+    (
+        COMPRESSED_RGB_PVRTC_4BPPV1_IMG,
+        COMPRESSED_SRGB_PVRTC_4BPPV1_EXT,
+    ),
+    (
+        COMPRESSED_RGBA_PVRTC_2BPPV1_IMG,
+        COMPRESSED_SRGB_ALPHA_PVRTC_2BPPV1_EXT,
+    ),
+    (
+        COMPRESSED_RGBA_PVRTC_4BPPV1_IMG,
+        COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT,
+    ),
+];
+
+/// OpenGL internal format, as stored in `glInternalFormat`.
+///
+/// # Example
+/// ```
+/// use ktx::format::{InternalFormat, COMPRESSED_RGBA_S3TC_DXT5_EXT};
+///
+/// let format = InternalFormat(COMPRESSED_RGBA_S3TC_DXT5_EXT);
+/// assert!(format.is_compressed());
+/// assert_eq!(format.block_dimensions(), Some((4, 4)));
+/// assert_eq!(format.block_size(), Some(16));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct InternalFormat(pub u32);
+
+impl InternalFormat {
+    /// Returns `true` if this is a known block compressed format.
+    #[inline]
+    pub fn is_compressed(self) -> bool {
+        self.compressed_block().is_some()
+    }
+
+    /// Returns the (width, height) in pixels of a block for known compressed formats.
+    #[inline]
+    pub fn block_dimensions(self) -> Option<(u32, u32)> {
+        self.compressed_block().map(|(w, h, _)| (w, h))
+    }
+
+    /// Returns the size in bytes of a block for known compressed formats.
+    #[inline]
+    pub fn block_size(self) -> Option<u32> {
+        self.compressed_block().map(|(.., size)| size)
+    }
+
+    /// Returns `true` if this is an `OES_compressed_paletted_texture` format, e.g.
+    /// `PALETTE8_RGBA8_OES`.
+    #[inline]
+    pub fn is_paletted(self) -> bool {
+        matches!(self.0, PALETTE4_RGB8_OES..=PALETTE8_RGB5_A1_OES)
+    }
+
+    /// Returns `true` if this is a known sRGB format.
+    #[inline]
+    pub fn is_srgb(self) -> bool {
+        self.to_linear_variant()
+            .is_some_and(|linear| linear != self)
+    }
+
+    /// Returns the sRGB variant of this format, or itself if already sRGB.
+    /// Returns `None` for formats without a known sRGB variant.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::format::*;
+    ///
+    /// let format = InternalFormat(COMPRESSED_RGBA_S3TC_DXT5_EXT);
+    /// assert_eq!(
+    ///     format.to_srgb_variant(),
+    ///     Some(InternalFormat(COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT))
+    /// );
+    /// ```
+    pub fn to_srgb_variant(self) -> Option<Self> {
+        let format = match self.0 {
+            COMPRESSED_RGBA_ASTC_4X4_KHR..=COMPRESSED_RGBA_ASTC_12X12_KHR => {
+                self.0 - COMPRESSED_RGBA_ASTC_4X4_KHR + COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR
+            }
+            COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR..=COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR => self.0,
+            f => SRGB_PAIRS
+                .iter()
+                .find(|(linear, srgb)| *linear == f || *srgb == f)
+                .map(|(_, srgb)| *srgb)?,
+        };
+        Some(Self(format))
+    }
+
+    /// Returns the linear variant of this format, or itself if already linear.
+    /// Returns `None` for formats without a known sRGB variant.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::format::*;
+    ///
+    /// let format = InternalFormat(COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT);
+    /// assert_eq!(
+    ///     format.to_linear_variant(),
+    ///     Some(InternalFormat(COMPRESSED_RGBA_S3TC_DXT5_EXT))
+    /// );
+    /// ```
+    pub fn to_linear_variant(self) -> Option<Self> {
+        let format = match self.0 {
+            COMPRESSED_RGBA_ASTC_4X4_KHR..=COMPRESSED_RGBA_ASTC_12X12_KHR => self.0,
+            COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR..=COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR => {
+                self.0 - COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR + COMPRESSED_RGBA_ASTC_4X4_KHR
+            }
+            f => SRGB_PAIRS
+                .iter()
+                .find(|(linear, srgb)| *linear == f || *srgb == f)
+                .map(|(linear, _)| *linear)?,
+        };
+        Some(Self(format))
+    }
+
+    /// Returns compressed (block width, block height, block bytes).
+    fn compressed_block(self) -> Option<(u32, u32, u32)> {
+        Some(match self.0 {
+            COMPRESSED_RGB_S3TC_DXT1_EXT
+            | COMPRESSED_RGBA_S3TC_DXT1_EXT
+            | COMPRESSED_SRGB_S3TC_DXT1_EXT
+            | COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT
+            | COMPRESSED_RED_RGTC1
+            | COMPRESSED_SIGNED_RED_RGTC1
+            | ETC1_RGB8_OES
+            | COMPRESSED_R11_EAC
+            | COMPRESSED_SIGNED_R11_EAC
+            | COMPRESSED_RGB8_ETC2
+            | COMPRESSED_SRGB8_ETC2
+            | COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2
+            | COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2
+            | ATC_RGB_AMD => (4, 4, 8),
+            COMPRESSED_RGBA_S3TC_DXT3_EXT
+            | COMPRESSED_RGBA_S3TC_DXT5_EXT
+            | COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT
+            | COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT
+            | COMPRESSED_RG_RGTC2
+            | COMPRESSED_SIGNED_RG_RGTC2
+            | COMPRESSED_RGBA_BPTC_UNORM
+            | COMPRESSED_SRGB_ALPHA_BPTC_UNORM
+            | COMPRESSED_RGB_BPTC_SIGNED_FLOAT
+            | COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT
+            | COMPRESSED_RG11_EAC
+            | COMPRESSED_SIGNED_RG11_EAC
+            | COMPRESSED_RGBA8_ETC2_EAC
+            | COMPRESSED_SRGB8_ALPHA8_ETC2_EAC
+            | ATC_RGBA_EXPLICIT_ALPHA_AMD
+            | ATC_RGBA_INTERPOLATED_ALPHA_AMD => (4, 4, 16),
+            COMPRESSED_RGB_PVRTC_4BPPV1_IMG
+            | COMPRESSED_RGBA_PVRTC_4BPPV1_IMG
+            | COMPRESSED_SRGB_PVRTC_4BPPV1_EXT
+            | COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT
+            | COMPRESSED_RGBA_PVRTC_4BPPV2_IMG => (4, 4, 8),
+            COMPRESSED_RGB_PVRTC_2BPPV1_IMG
+            | COMPRESSED_RGBA_PVRTC_2BPPV1_IMG
+            | COMPRESSED_SRGB_PVRTC_2BPPV1_EXT
+            | COMPRESSED_SRGB_ALPHA_PVRTC_2BPPV1_EXT
+            | COMPRESSED_RGBA_PVRTC_2BPPV2_IMG => (8, 4, 8),
+            COMPRESSED_RGBA_ASTC_4X4_KHR..=COMPRESSED_RGBA_ASTC_12X12_KHR => {
+                let (w, h) = ASTC_BLOCKS[(self.0 - COMPRESSED_RGBA_ASTC_4X4_KHR) as usize];
+                (w, h, 16)
+            }
+            COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR..=COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR => {
+                let index = self.0 - COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR;
+                let (w, h) = ASTC_BLOCKS[index as usize];
+                (w, h, 16)
+            }
+            _ => return None,
+        })
+    }
+}
+
+impl From<u32> for InternalFormat {
+    #[inline]
+    fn from(value: u32) -> Self {
+        Self(value)
+    }
+}
+
+/// Returns `(row count, unpadded row length)` of a single image, i.e. one face of one
+/// array element, at the input level. Compressed format rows are rows of blocks.
+/// Returns `None` for unknown formats.
+pub(crate) fn image_rows(
+    info: &(impl crate::KtxInfo + ?Sized),
+    level: u32,
+) -> Option<(usize, usize)> {
+    let (w, h, d) = info.level_dimensions(level);
+    let (w, h, d) = (w as usize, h as usize, d as usize);
+
+    if info.is_compressed() {
+        let format = info.internal_format();
+        let (block_w, block_h) = format.block_dimensions()?;
+        let mut blocks_x = w.div_ceil(block_w as usize);
+        let mut blocks_y = h.div_ceil(block_h as usize);
+        if matches!(
+            format.0,
+            COMPRESSED_RGB_PVRTC_4BPPV1_IMG..=COMPRESSED_RGBA_PVRTC_2BPPV1_IMG
+                | COMPRESSED_SRGB_PVRTC_2BPPV1_EXT..=COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT
+        ) {
+            // PVRTC1 textures are at least 2x2 blocks
+            blocks_x = blocks_x.max(2);
+            blocks_y = blocks_y.max(2);
+        }
+        Some((blocks_y * d, blocks_x * format.block_size()? as usize))
+    } else {
+        let pixel_size = pixel_size(info.gl_type(), info.gl_format())? as usize;
+        Some((h * d, w * pixel_size))
+    }
+}
+
+/// Returns the expected data length of a complete level, including all array elements
+/// & faces, or `Ok(None)` if the format is unknown.
+///
+/// Errors if the length overflows, e.g. with untrusted `faces` & `array_elements` values.
+#[cfg(feature = "std")]
+pub(crate) fn expected_level_len(
+    info: &impl crate::KtxInfo,
+    level: u32,
+) -> Result<Option<usize>, DimensionsOverflow> {
+    let (rows, row_len) = match image_rows(info, level) {
+        Some(rows) => rows,
+        None => return Ok(None),
+    };
+    // rows are aligned to 4 bytes, i.e. GL_UNPACK_ALIGNMENT 4
+    rows.checked_mul(padded_len(row_len))
+        .and_then(|len| len.checked_mul(info.faces().max(1) as usize))
+        .and_then(|len| len.checked_mul(info.array_elements().max(1) as usize))
+        .map(Some)
+        .ok_or(DimensionsOverflow)
+}
+
+/// Header dimensions, `faces` & `array_elements` describe a level too large to address,
+/// see [`expected_level_len`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct DimensionsOverflow;
+
+/// Returns the length of each of `images` equally sized parts of a level, e.g. faces or
+/// array elements, erroring if the level length does not split evenly.
+pub(crate) fn split_level_len(
+    level: u32,
+    level_len: u64,
+    images: u64,
+) -> Result<u64, crate::error::KtxError> {
+    match level_len.checked_rem(images) {
+        Some(0) => Ok(level_len / images),
+        _ => Err(crate::error::KtxError::UnevenLevel { level }),
+    }
+}
+
+/// Returns the padding needed after data of the input length to align to 4 bytes,
+/// e.g. `mipPadding`.
+#[inline]
+pub(crate) const fn padding_len(len: u64) -> u64 {
+    (4 - len % 4) % 4
+}
+
+/// Returns the length rounded up to a multiple of 4.
+#[inline]
+pub(crate) fn padded_len(len: usize) -> usize {
+    (len + 3) & !3
+}
+
+/// Returns `true` for non-array cubemaps, whose `imageSize` is the size of each face rather
+/// than the whole level, each face followed by `cubePadding`, see
+/// https://www.khronos.org/registry/KTX/specs/1.0/ktxspec_v1.html#2.16
+#[inline]
+pub(crate) const fn has_face_image_size(header: &crate::header::KtxHeader) -> bool {
+    header.array_elements == 0 && header.faces == 6
+}
+
+/// Returns the `imageSize` value of level data of the input length, excluding padding,
+/// or `None` if too large for a `u32`.
+#[cfg(feature = "std")]
+#[inline]
+pub(crate) fn image_size_field(level_len: usize, header: &crate::header::KtxHeader) -> Option<u32> {
+    let image_size = match has_face_image_size(header) {
+        true => level_len / 6,
+        false => level_len,
+    };
+    core::convert::TryFrom::try_from(image_size).ok()
+}
+
+/// Returns the length of the level data following an `imageSize` value, excluding
+/// `mipPadding`, see [`has_face_image_size`].
+#[inline]
+pub(crate) const fn level_data_len(image_size: u32, header: &crate::header::KtxHeader) -> u64 {
+    let image_size = image_size as u64;
+    match has_face_image_size(header) {
+        true => (image_size + padding_len(image_size)) * 6,
+        false => image_size,
+    }
+}
+
+/// Returns the size in bytes of a single pixel of uncompressed data with the
+/// input `glType` & `glFormat`.
+pub(crate) fn pixel_size(gl_type: u32, gl_format: u32) -> Option<u32> {
+    match gl_type {
+        UNSIGNED_BYTE_3_3_2 | UNSIGNED_BYTE_2_3_3_REV => return Some(1),
+        UNSIGNED_SHORT_5_6_5
+        | UNSIGNED_SHORT_5_6_5_REV
+        | UNSIGNED_SHORT_4_4_4_4
+        | UNSIGNED_SHORT_4_4_4_4_REV
+        | UNSIGNED_SHORT_5_5_5_1
+        | UNSIGNED_SHORT_1_5_5_5_REV => return Some(2),
+        UNSIGNED_INT_8_8_8_8
+        | UNSIGNED_INT_8_8_8_8_REV
+        | UNSIGNED_INT_10_10_10_2
+        | UNSIGNED_INT_2_10_10_10_REV
+        | UNSIGNED_INT_24_8
+        | UNSIGNED_INT_10F_11F_11F_REV
+        | UNSIGNED_INT_5_9_9_9_REV => return Some(4),
+        FLOAT_32_UNSIGNED_INT_24_8_REV => return Some(8),
+        _ => {}
+    }
+
+    let component_size = match gl_type {
+        BYTE | UNSIGNED_BYTE => 1,
+        SHORT | UNSIGNED_SHORT | HALF_FLOAT | HALF_FLOAT_OES => 2,
+        INT | UNSIGNED_INT | FLOAT => 4,
+        _ => return None,
+    };
+    let components = match gl_format {
+        STENCIL_INDEX | DEPTH_COMPONENT | RED | GREEN | BLUE | ALPHA | LUMINANCE | RED_INTEGER
+        | GREEN_INTEGER | BLUE_INTEGER | ALPHA_INTEGER => 1,
+        LUMINANCE_ALPHA | RG | RG_INTEGER | DEPTH_STENCIL => 2,
+        RGB | BGR | RGB_INTEGER | BGR_INTEGER => 3,
+        RGBA | BGRA | RGBA_INTEGER | BGRA_INTEGER => 4,
+        _ => return None,
+    };
+    Some(component_size * components)
+}
@@ -0,0 +1,280 @@
+//! OpenGL texture upload using [glow](https://docs.rs/glow).
+//!
+//! Requires the `gl` feature.
+use crate::{
+    header::{KtxInfo, TextureKind},
+    key_value::Channel,
+    slice::Ktx,
+};
+use glow::{CompressedPixelUnpackData, HasContext, PixelUnpackData};
+use std::borrow::Cow;
+
+/// An uploaded OpenGL texture.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlTexture<T> {
+    /// Texture object containing the uploaded data.
+    pub texture: T,
+    /// Texture target the texture is bound to, e.g. `glow::TEXTURE_2D`.
+    pub target: u32,
+}
+
+/// Returns the texture target the KTX data should be uploaded to, e.g. `glow::TEXTURE_CUBE_MAP`.
+pub fn texture_target(info: &impl KtxInfo) -> u32 {
+    match info.kind() {
+        TextureKind::D1 => glow::TEXTURE_1D,
+        TextureKind::D2 => glow::TEXTURE_2D,
+        TextureKind::D3 => glow::TEXTURE_3D,
+        TextureKind::Cube => glow::TEXTURE_CUBE_MAP,
+        TextureKind::D1Array => glow::TEXTURE_1D_ARRAY,
+        TextureKind::D2Array => glow::TEXTURE_2D_ARRAY,
+        TextureKind::CubeArray => glow::TEXTURE_CUBE_MAP_ARRAY,
+    }
+}
+
+/// Creates a new texture & uploads all KTX texture levels, leaving it bound to its target.
+///
+/// Follows the reference libktx loader behaviour:
+/// * Texture storage is allocated with `glTexStorage*`, level data is uploaded with
+///   `glCompressedTexSubImage*` for compressed formats & `glTexSubImage*` otherwise.
+///   1D textures use `glTexImage1D`/`glCompressedTexImage1D`.
+/// * Cubemap faces are uploaded individually to each `TEXTURE_CUBE_MAP_POSITIVE_X + face` target.
+/// * Array layers & cubemap array layer-faces are uploaded as slices of a 3D/2D array call.
+/// * When `mipmap_levels` is `0` level 0 is uploaded & the remaining levels are generated.
+/// * `KTXswizzle` metadata is applied with the `TEXTURE_SWIZZLE_*` texture parameters.
+/// * Texture data is endian converted when `gl_type_size > 1` & the data endianness
+///   differs from the native endianness.
+///
+/// The `UNPACK_ALIGNMENT` is set to `4` during upload, then restored.
+///
+/// # Safety
+/// The GL context must be current & support texture storage (GL 4.2 or GLES 3.0).
+///
+/// # Panics
+/// The KTX data is missing texture levels.
+pub unsafe fn upload<C, D>(gl: &C, ktx: &Ktx<D>) -> Result<GlTexture<C::Texture>, String>
+where
+    C: HasContext,
+    D: AsRef<[u8]>,
+{
+    let target = texture_target(ktx);
+    let texture = gl.create_texture()?;
+    gl.bind_texture(target, Some(texture));
+
+    let prev_alignment = gl.get_parameter_i32(glow::UNPACK_ALIGNMENT);
+    gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, 4);
+
+    let compressed = ktx.is_compressed();
+    let internal_format = ktx.gl_internal_format();
+    let generate_mipmaps = ktx.mipmap_levels() == 0;
+    let stored_levels = ktx.mipmap_levels().max(1);
+
+    let [levels, width, height, depth] = storage_size(ktx, target);
+    match target {
+        glow::TEXTURE_1D => {}
+        glow::TEXTURE_1D_ARRAY | glow::TEXTURE_2D | glow::TEXTURE_CUBE_MAP => {
+            gl.tex_storage_2d(target, levels, internal_format, width, height)
+        }
+        _ => gl.tex_storage_3d(target, levels, internal_format, width, height, depth),
+    }
+
+    for level in 0..stored_levels {
+        let data = native_endian(ktx, ktx.texture_level(level));
+        let lvl = level as i32;
+        let [w, h, depth] = level_size(ktx, target, level);
+
+        match target {
+            glow::TEXTURE_1D if compressed => {
+                let len = data.len() as i32;
+                gl.compressed_tex_image_1d(target, lvl, internal_format as _, w, 0, len, &data);
+            }
+            glow::TEXTURE_1D => {
+                let pixels = PixelUnpackData::Slice(Some(&data));
+                let (format, ty) = (ktx.gl_format(), ktx.gl_type());
+                gl.tex_image_1d(target, lvl, internal_format as _, w, 0, format, ty, pixels);
+            }
+            glow::TEXTURE_CUBE_MAP => {
+                let face_len = data.len() / 6;
+                for (face, face_data) in data.chunks_exact(face_len).enumerate() {
+                    let face_target = glow::TEXTURE_CUBE_MAP_POSITIVE_X + face as u32;
+                    sub_image_2d(gl, ktx, face_target, lvl, w, h, face_data);
+                }
+            }
+            glow::TEXTURE_1D_ARRAY | glow::TEXTURE_2D => {
+                sub_image_2d(gl, ktx, target, lvl, w, h, &data)
+            }
+            _ => {
+                if compressed {
+                    let pixels = CompressedPixelUnpackData::Slice(&data);
+                    gl.compressed_tex_sub_image_3d(
+                        target,
+                        lvl,
+                        0,
+                        0,
+                        0,
+                        w,
+                        h,
+                        depth,
+                        internal_format,
+                        pixels,
+                    );
+                } else {
+                    let pixels = PixelUnpackData::Slice(Some(&data));
+                    let (format, ty) = (ktx.gl_format(), ktx.gl_type());
+                    gl.tex_sub_image_3d(target, lvl, 0, 0, 0, w, h, depth, format, ty, pixels);
+                }
+            }
+        }
+    }
+
+    if generate_mipmaps {
+        gl.generate_mipmap(target);
+    }
+
+    if let Some(swizzle) = ktx.swizzle() {
+        for (param, channel) in [
+            glow::TEXTURE_SWIZZLE_R,
+            glow::TEXTURE_SWIZZLE_G,
+            glow::TEXTURE_SWIZZLE_B,
+            glow::TEXTURE_SWIZZLE_A,
+        ]
+        .iter()
+        .zip(swizzle.iter())
+        {
+            let channel = match channel {
+                Channel::Red => glow::RED,
+                Channel::Green => glow::GREEN,
+                Channel::Blue => glow::BLUE,
+                Channel::Alpha => glow::ALPHA,
+                Channel::Zero => glow::ZERO,
+                Channel::One => glow::ONE,
+            };
+            gl.tex_parameter_i32(target, *param, channel as i32);
+        }
+    }
+
+    gl.pixel_store_i32(glow::UNPACK_ALIGNMENT, prev_alignment);
+
+    Ok(GlTexture { texture, target })
+}
+
+/// Returns the `glTexStorage*` `[levels, width, height, depth]` for the texture target.
+///
+/// 1D array layers are the height, 2D array & cubemap array layer-faces the depth.
+/// When `mipmap_levels` is `0` storage is allocated for the full mipmap chain.
+fn storage_size(info: &impl KtxInfo, target: u32) -> [i32; 4] {
+    let levels = match info.mipmap_levels() {
+        0 => info.level_count_for_full_chain(),
+        levels => levels,
+    };
+    let [width, height, depth] = level_size(info, target, 0);
+    [levels as i32, width, height, depth]
+}
+
+/// Returns the `[width, height, depth]` of a level upload to the texture target, with array
+/// layers, or cubemap array layer-faces, as the height of 1D arrays & depth of 2D arrays.
+fn level_size(info: &impl KtxInfo, target: u32, level: u32) -> [i32; 3] {
+    let (width, height, depth) = info.level_dimensions(level);
+    let layers = info.array_elements().max(1) as i32;
+    let (width, height, depth) = (width as i32, height as i32, depth as i32);
+    match target {
+        glow::TEXTURE_1D => [width, 1, 1],
+        glow::TEXTURE_1D_ARRAY => [width, layers, 1],
+        glow::TEXTURE_3D => [width, height, depth],
+        glow::TEXTURE_2D_ARRAY => [width, height, layers],
+        glow::TEXTURE_CUBE_MAP_ARRAY => [width, height, layers * 6],
+        _ => [width, height, 1],
+    }
+}
+
+unsafe fn sub_image_2d<C: HasContext>(
+    gl: &C,
+    info: &impl KtxInfo,
+    target: u32,
+    level: i32,
+    width: i32,
+    height: i32,
+    data: &[u8],
+) {
+    if info.is_compressed() {
+        let pixels = CompressedPixelUnpackData::Slice(data);
+        let format = info.gl_internal_format();
+        gl.compressed_tex_sub_image_2d(target, level, 0, 0, width, height, format, pixels);
+    } else {
+        let pixels = PixelUnpackData::Slice(Some(data));
+        let (format, ty) = (info.gl_format(), info.gl_type());
+        gl.tex_sub_image_2d(target, level, 0, 0, width, height, format, ty, pixels);
+    }
+}
+
+/// Returns level data converted to native endianness if required.
+fn native_endian<'a>(info: &impl KtxInfo, data: &'a [u8]) -> Cow<'a, [u8]> {
+    let type_size = info.gl_type_size() as usize;
+    if info.big_endian() == cfg!(target_endian = "big") || !matches!(type_size, 2 | 4) {
+        return Cow::Borrowed(data);
+    }
+    let mut data = data.to_vec();
+    for value in data.chunks_exact_mut(type_size) {
+        value.reverse();
+    }
+    Cow::Owned(data)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::header::KtxHeader;
+
+    fn header(width: u32, height: u32, depth: u32, faces: u32, layers: u32) -> KtxHeader {
+        *crate::KtxBuilder::new()
+            .pixel_width(width)
+            .pixel_height(height)
+            .pixel_depth(depth)
+            .faces(faces)
+            .array_elements(layers)
+            .mipmap_levels(0)
+            .as_ref()
+    }
+
+    #[test]
+    fn texture_targets() {
+        assert_eq!(texture_target(&header(8, 0, 0, 1, 0)), glow::TEXTURE_1D);
+        assert_eq!(
+            texture_target(&header(8, 0, 0, 1, 3)),
+            glow::TEXTURE_1D_ARRAY
+        );
+        assert_eq!(texture_target(&header(8, 4, 0, 1, 0)), glow::TEXTURE_2D);
+        assert_eq!(
+            texture_target(&header(8, 4, 0, 1, 3)),
+            glow::TEXTURE_2D_ARRAY
+        );
+        assert_eq!(texture_target(&header(8, 4, 2, 1, 0)), glow::TEXTURE_3D);
+        assert_eq!(
+            texture_target(&header(8, 8, 0, 6, 0)),
+            glow::TEXTURE_CUBE_MAP
+        );
+        assert_eq!(
+            texture_target(&header(8, 8, 0, 6, 3)),
+            glow::TEXTURE_CUBE_MAP_ARRAY
+        );
+    }
+
+    #[test]
+    fn storage_and_level_sizes() {
+        let size = |header: KtxHeader, level| {
+            let target = texture_target(&header);
+            (
+                storage_size(&header, target),
+                level_size(&header, target, level),
+            )
+        };
+
+        // mipmap_levels 0 allocates the full chain of 4 levels
+        assert_eq!(size(header(8, 0, 0, 1, 0), 1), ([4, 8, 1, 1], [4, 1, 1]));
+        assert_eq!(size(header(8, 0, 0, 1, 3), 1), ([4, 8, 3, 1], [4, 3, 1]));
+        assert_eq!(size(header(8, 4, 0, 1, 0), 3), ([4, 8, 4, 1], [1, 1, 1]));
+        assert_eq!(size(header(8, 4, 0, 1, 3), 1), ([4, 8, 4, 3], [4, 2, 3]));
+        assert_eq!(size(header(8, 4, 2, 1, 0), 1), ([4, 8, 4, 2], [4, 2, 1]));
+        assert_eq!(size(header(8, 8, 0, 6, 0), 2), ([4, 8, 8, 1], [2, 2, 1]));
+        assert_eq!(size(header(8, 8, 0, 6, 3), 1), ([4, 8, 8, 18], [4, 4, 18]));
+    }
+}
@@ -1,4 +1,9 @@
+use crate::{
+    error::HeaderError,
+    format::{self, InternalFormat},
+};
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
+use core::fmt;
 
 pub(crate) const KTX1_IDENTIFIER: [u8; 12] = [
     0xAB, 0x4B, 0x54, 0x58, 0x20, 0x31, 0x31, 0xBB, 0x0D, 0x0A, 0x1A, 0x0A,
@@ -96,56 +101,465 @@ pub trait KtxInfo {
     /// KTX files must not assume that value fields are NUL terminated). keyAndValueByteSize does
     /// not include the bytes in valuePadding.
     fn bytes_of_key_value_data(&self) -> u32;
+
+    /// Returns `gl_internal_format` as an [`InternalFormat`](../format/struct.InternalFormat.html).
+    #[inline]
+    fn internal_format(&self) -> InternalFormat {
+        InternalFormat(self.gl_internal_format())
+    }
+
+    /// Returns `true` for compressed textures, i.e. `gl_type` is `0`.
+    #[inline]
+    fn is_compressed(&self) -> bool {
+        self.gl_type() == 0
+    }
+
+    /// Returns the (width, height) in pixels of a compressed block.
+    ///
+    /// Uncompressed textures, and compressed textures of an unknown format, return `(1, 1)`.
+    #[inline]
+    fn block_dimensions(&self) -> (u32, u32) {
+        match self.is_compressed() {
+            true => self.internal_format().block_dimensions().unwrap_or((1, 1)),
+            false => (1, 1),
+        }
+    }
+
+    /// Returns the size in bytes of a compressed block, or of a single pixel for uncompressed
+    /// textures. Returns `None` if the format or type is unknown.
+    #[inline]
+    fn bytes_per_block_or_pixel(&self) -> Option<u32> {
+        match self.is_compressed() {
+            true => self.internal_format().block_size(),
+            false => format::pixel_size(self.gl_type(), self.gl_format()),
+        }
+    }
+
+    /// Returns `true` if `gl_internal_format` is a known sRGB format.
+    #[inline]
+    fn is_srgb(&self) -> bool {
+        self.internal_format().is_srgb()
+    }
+
+    /// Returns `true` for cubemaps & cubemap arrays, i.e. `faces` is `6`.
+    #[inline]
+    fn is_cubemap(&self) -> bool {
+        self.faces() == 6
+    }
+
+    /// Returns `true` for array textures, i.e. `array_elements` is non-zero.
+    #[inline]
+    fn is_array(&self) -> bool {
+        self.array_elements() > 0
+    }
+
+    /// Returns the `(width, height, depth)` in pixels of the input level, starting at `0`,
+    /// i.e. `max(1, size >> level)` for each dimension.
+    ///
+    /// A `0` height or depth, e.g. for 1D or 2D textures, is returned as `1`.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{include_ktx, KtxInfo};
+    ///
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// assert_eq!(image.level_dimensions(0), (260, 200, 1));
+    /// assert_eq!(image.level_dimensions(7), (2, 1, 1));
+    /// ```
+    #[inline]
+    fn level_dimensions(&self, level: u32) -> (u32, u32, u32) {
+        let dim = |d: u32| d.checked_shr(level).unwrap_or(0).max(1);
+        (
+            dim(self.pixel_width()),
+            dim(self.pixel_height()),
+            dim(self.pixel_depth()),
+        )
+    }
+
+    /// Returns the number of levels in a full mipmap pyramid for the level 0 dimensions,
+    /// down to a `1x1x1` level.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{include_ktx, KtxInfo};
+    ///
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// assert_eq!(image.level_count_for_full_chain(), 9);
+    /// ```
+    #[inline]
+    fn level_count_for_full_chain(&self) -> u32 {
+        let max_dim = self
+            .pixel_width()
+            .max(self.pixel_height())
+            .max(self.pixel_depth());
+        32 - max_dim.max(1).leading_zeros()
+    }
+
+    /// Returns an estimate of the GPU memory in bytes used by the texture, including all
+    /// levels, faces & array elements. Returns `None` if the format or type is unknown, or the
+    /// size overflows `u64`.
+    ///
+    /// When `mipmap_levels` is `0` the full generated mipmap chain is included. Rows, or rows
+    /// of compressed blocks, are aligned to `row_pitch_alignment` bytes, e.g. `1` for
+    /// tightly packed rows or `4` for KTX level data layout.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{include_ktx, KtxInfo};
+    ///
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// assert_eq!(image.estimated_gpu_size(1), Some(image.total_texture_bytes() as u64));
+    /// ```
+    fn estimated_gpu_size(&self, row_pitch_alignment: u32) -> Option<u64> {
+        let levels = match self.mipmap_levels() {
+            0 => self.level_count_for_full_chain(),
+            n => n,
+        };
+        let align = row_pitch_alignment.max(1) as u64;
+        let images = self.faces().max(1) as u64 * self.array_elements().max(1) as u64;
+
+        let mut size = 0;
+        for level in 0..levels {
+            let (rows, row_len) = format::image_rows(self, level)?;
+            let row_pitch = (row_len as u64).div_ceil(align) * align;
+            let level_size = (rows as u64).checked_mul(row_pitch)?.checked_mul(images)?;
+            size = level_size.checked_add(size)?;
+        }
+        Some(size)
+    }
+
+    /// Returns the kind of texture, derived from `pixel_height`, `pixel_depth`, `faces` &
+    /// `array_elements`.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{header::TextureKind, include_ktx, KtxInfo};
+    ///
+    /// assert_eq!(include_ktx!("../tests/babg-bc3.ktx").kind(), TextureKind::D2);
+    /// assert_eq!(include_ktx!("../tests/uffizi_rgba16f_cube.ktx").kind(), TextureKind::Cube);
+    /// ```
+    fn kind(&self) -> TextureKind {
+        match (self.is_array(), self.is_cubemap()) {
+            (false, true) => TextureKind::Cube,
+            (true, true) => TextureKind::CubeArray,
+            _ if self.pixel_depth() > 0 => TextureKind::D3,
+            (false, _) if self.pixel_height() == 0 => TextureKind::D1,
+            (true, _) if self.pixel_height() == 0 => TextureKind::D1Array,
+            (false, _) => TextureKind::D2,
+            (true, _) => TextureKind::D2Array,
+        }
+    }
+}
+
+/// Kind of texture, see [`KtxInfo::kind`](trait.KtxInfo.html#method.kind).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TextureKind {
+    /// 1D texture, `pixel_height` & `pixel_depth` are `0`.
+    D1,
+    /// 2D texture, `pixel_depth` is `0`.
+    D2,
+    /// 3D texture, `pixel_depth` is non-zero.
+    D3,
+    /// Cubemap texture, `faces` is `6`.
+    Cube,
+    /// 1D array texture.
+    D1Array,
+    /// 2D array texture.
+    D2Array,
+    /// Cubemap array texture.
+    CubeArray,
+}
+
+impl fmt::Display for TextureKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::D1 => "1D",
+            Self::D2 => "2D",
+            Self::D3 => "3D",
+            Self::Cube => "cubemap",
+            Self::D1Array => "1D array",
+            Self::D2Array => "2D array",
+            Self::CubeArray => "cubemap array",
+        })
+    }
+}
+
+/// Returns `true` if the data starts with the KTX1 file identifier.
+pub(crate) const fn has_ktx1_identifier(data: &[u8]) -> bool {
+    if data.len() < KTX1_IDENTIFIER.len() {
+        return false;
+    }
+    let mut idx = 0;
+    while idx < KTX1_IDENTIFIER.len() {
+        if data[idx] != KTX1_IDENTIFIER[idx] {
+            return false;
+        }
+        idx += 1;
+    }
+    true
+}
+
+/// Reads a `u32` at the byte offset.
+#[inline]
+pub(crate) const fn read_u32(data: &[u8], offset: usize, big_endian: bool) -> u32 {
+    let bytes = [
+        data[offset],
+        data[offset + 1],
+        data[offset + 2],
+        data[offset + 3],
+    ];
+    match big_endian {
+        true => u32::from_be_bytes(bytes),
+        false => u32::from_le_bytes(bytes),
+    }
 }
 
 /// KTX texture storage format header. Provides [`KtxInfo`](../header/trait.KtxInfo.html).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
 pub struct KtxHeader {
-    big_endian: bool,
-    gl_type: u32,
-    gl_type_size: u32,
-    gl_format: u32,
-    gl_internal_format: u32,
-    gl_base_internal_format: u32,
-    pixel_width: u32,
-    pixel_height: u32,
-    pixel_depth: u32,
-    array_elements: u32,
-    faces: u32,
-    mipmap_levels: u32,
-    bytes_of_key_value_data: u32,
+    pub(crate) big_endian: bool,
+    pub(crate) gl_type: u32,
+    pub(crate) gl_type_size: u32,
+    pub(crate) gl_format: u32,
+    pub(crate) gl_internal_format: u32,
+    pub(crate) gl_base_internal_format: u32,
+    pub(crate) pixel_width: u32,
+    pub(crate) pixel_height: u32,
+    pub(crate) pixel_depth: u32,
+    pub(crate) array_elements: u32,
+    pub(crate) faces: u32,
+    pub(crate) mipmap_levels: u32,
+    pub(crate) bytes_of_key_value_data: u32,
 }
 
 impl KtxHeader {
     /// Reads first 64 bytes to parse KTX header data, returns a `KtxHeader`.
-    pub fn new(first_64_bytes: &[u8]) -> Self {
+    ///
+    /// This is a `const fn` so may be used to parse headers at compile time.
+    pub const fn new(first_64_bytes: &[u8]) -> Self {
         debug_assert!(first_64_bytes.len() >= 64);
-        debug_assert_eq!(&first_64_bytes[..12], &KTX1_IDENTIFIER, "Not KTX1");
+        debug_assert!(has_ktx1_identifier(first_64_bytes), "Not KTX1");
 
         let big_endian = first_64_bytes[12] == 4;
+        let bytes = first_64_bytes;
+
+        Self {
+            big_endian,
+            gl_type: read_u32(bytes, 16, big_endian),
+            gl_type_size: read_u32(bytes, 20, big_endian),
+            gl_format: read_u32(bytes, 24, big_endian),
+            gl_internal_format: read_u32(bytes, 28, big_endian),
+            gl_base_internal_format: read_u32(bytes, 32, big_endian),
+            pixel_width: read_u32(bytes, 36, big_endian),
+            pixel_height: read_u32(bytes, 40, big_endian),
+            pixel_depth: read_u32(bytes, 44, big_endian),
+            array_elements: read_u32(bytes, 48, big_endian),
+            faces: read_u32(bytes, 52, big_endian),
+            mipmap_levels: read_u32(bytes, 56, big_endian),
+            bytes_of_key_value_data: read_u32(bytes, 60, big_endian),
+        }
+    }
+
+    /// Parses & validates a KTX header from a prefix of KTX data, e.g. the first chunk of a
+    /// file fetched with an HTTP range request.
+    ///
+    /// Only the first 64 bytes are read, so a prefix of at least 64 bytes is always sufficient.
+    /// Key/value data follows in the next
+    /// [`bytes_of_key_value_data`](trait.KtxInfo.html#tymethod.bytes_of_key_value_data) bytes.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{header::KtxHeader, HeaderError, KtxInfo};
+    ///
+    /// let data = include_bytes!("../tests/babg-bc3.ktx");
+    /// let header = KtxHeader::try_from_prefix(&data[..64])?;
+    /// assert_eq!(header.pixel_width(), 260);
+    ///
+    /// assert_eq!(KtxHeader::try_from_prefix(&data[..63]), Err(HeaderError::TooShort));
+    /// # Ok::<(), HeaderError>(())
+    /// ```
+    pub const fn try_from_prefix(prefix: &[u8]) -> Result<Self, HeaderError> {
+        if prefix.len() < 64 {
+            return Err(HeaderError::TooShort);
+        }
+        if !has_ktx1_identifier(prefix) {
+            return Err(HeaderError::InvalidIdentifier);
+        }
+        match read_u32(prefix, 12, false) {
+            0x0403_0201 | 0x0102_0304 => Ok(Self::new(prefix)),
+            _ => Err(HeaderError::InvalidEndianness),
+        }
+    }
+
+    /// Parses & validates a complete 64 byte KTX header.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{header::KtxHeader, HeaderError};
+    ///
+    /// let data = include_bytes!("../tests/babg-bc3.ktx");
+    /// let mut bytes = [0; 64];
+    /// bytes.copy_from_slice(&data[..64]);
+    /// let header = KtxHeader::try_from_bytes(&bytes)?;
+    /// assert_eq!(header.to_bytes(), bytes);
+    ///
+    /// assert_eq!(KtxHeader::try_from_bytes(&[0; 64]), Err(HeaderError::InvalidIdentifier));
+    /// # Ok::<(), HeaderError>(())
+    /// ```
+    #[inline]
+    pub const fn try_from_bytes(bytes: &[u8; 64]) -> Result<Self, HeaderError> {
+        Self::try_from_prefix(bytes)
+    }
 
-        let mut vals: [u32; 12] = <_>::default();
-        if big_endian {
-            BigEndian::read_u32_into(&first_64_bytes[16..64], &mut vals);
+    /// Returns the 64 byte KTX header data, written with the header endianness.
+    ///
+    /// Round trips with [`KtxHeader::try_from_bytes`](#method.try_from_bytes).
+    #[inline]
+    pub fn to_bytes(&self) -> [u8; 64] {
+        let mut bytes = [0; 64];
+        self.write(&mut bytes);
+        bytes
+    }
+
+    /// Writes KTX header data into the first 64 bytes of the input slice.
+    ///
+    /// Prefer [`KtxHeader::to_bytes`](#method.to_bytes).
+    ///
+    /// # Panics
+    ///
+    /// Input slice length is < 64.
+    pub fn write(&self, first_64_bytes: &mut [u8]) {
+        first_64_bytes[..12].copy_from_slice(&KTX1_IDENTIFIER);
+
+        let vals = [
+            0x0403_0201,
+            self.gl_type,
+            self.gl_type_size,
+            self.gl_format,
+            self.gl_internal_format,
+            self.gl_base_internal_format,
+            self.pixel_width,
+            self.pixel_height,
+            self.pixel_depth,
+            self.array_elements,
+            self.faces,
+            self.mipmap_levels,
+            self.bytes_of_key_value_data,
+        ];
+        if self.big_endian {
+            BigEndian::write_u32_into(&vals, &mut first_64_bytes[12..64]);
         } else {
-            LittleEndian::read_u32_into(&first_64_bytes[16..64], &mut vals);
+            LittleEndian::write_u32_into(&vals, &mut first_64_bytes[12..64]);
         }
+    }
 
-        Self {
-            big_endian,
-            gl_type: vals[0],
-            gl_type_size: vals[1],
-            gl_format: vals[2],
-            gl_internal_format: vals[3],
-            gl_base_internal_format: vals[4],
-            pixel_width: vals[5],
-            pixel_height: vals[6],
-            pixel_depth: vals[7],
-            array_elements: vals[8],
-            faces: vals[9],
-            mipmap_levels: vals[10],
-            bytes_of_key_value_data: vals[11],
+    /// Returns a displayable form of the pixel dimensions of the input level, e.g. `65x50`,
+    /// omitting height & depth when `0` in the header.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// assert_eq!(image.as_ref().level_dimensions_display(2).to_string(), "65x50");
+    /// ```
+    #[inline]
+    pub fn level_dimensions_display(&self, level: u32) -> impl fmt::Display + '_ {
+        Dimensions {
+            header: self,
+            level,
+        }
+    }
+}
+
+/// Human-readable header summary using GL constant names.
+///
+/// The alternate form, `{:#}`, writes a multi-line report of every header value.
+///
+/// # Example
+/// ```
+/// # use ktx::*;
+/// let image = include_ktx!("../tests/babg-bc3.ktx");
+/// assert_eq!(
+///     image.as_ref().to_string(),
+///     "2D 260x200 COMPRESSED_RGBA_S3TC_DXT5_EXT, 8 levels"
+/// );
+/// println!("{:#}", image.as_ref());
+/// ```
+impl fmt::Display for KtxHeader {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let kind = self.kind();
+        if f.alternate() {
+            writeln!(f, "kind: {kind}")?;
+            writeln!(f, "dimensions: {}", self.level_dimensions_display(0))?;
+            let names = [
+                ("glType", self.gl_type, format::type_name(self.gl_type)),
+                (
+                    "glFormat",
+                    self.gl_format,
+                    format::format_name(self.gl_format),
+                ),
+                (
+                    "glInternalFormat",
+                    self.gl_internal_format,
+                    format::internal_format_name(self.gl_internal_format),
+                ),
+                (
+                    "glBaseInternalFormat",
+                    self.gl_base_internal_format,
+                    format::format_name(self.gl_base_internal_format),
+                ),
+            ];
+            for (field, value, name) in names {
+                match name {
+                    Some(name) => writeln!(f, "{field}: {name} ({value:#x})")?,
+                    None => writeln!(f, "{field}: {value:#x}")?,
+                }
+            }
+            writeln!(f, "glTypeSize: {}", self.gl_type_size)?;
+            writeln!(f, "arrayElements: {}", self.array_elements)?;
+            writeln!(f, "faces: {}", self.faces)?;
+            writeln!(f, "mipmapLevels: {}", self.mipmap_levels)?;
+            writeln!(f, "bytesOfKeyValueData: {}", self.bytes_of_key_value_data)?;
+            let endianness = match self.big_endian {
+                true => "big",
+                false => "little",
+            };
+            write!(f, "endianness: {endianness}")
+        } else {
+            write!(f, "{kind} {}", self.level_dimensions_display(0))?;
+            match format::internal_format_name(self.gl_internal_format) {
+                Some(name) => write!(f, " {name}")?,
+                None => write!(f, " {:#x}", self.gl_internal_format)?,
+            }
+            let levels = self.mipmap_levels.max(1);
+            write!(f, ", {levels} level{}", if levels == 1 { "" } else { "s" })?;
+            if self.is_array() {
+                let layers = self.array_elements;
+                write!(f, ", {layers} layer{}", if layers == 1 { "" } else { "s" })?;
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Displays level pixel dimensions, e.g. `260x200`, omitting `0` header height & depth.
+struct Dimensions<'a> {
+    header: &'a KtxHeader,
+    level: u32,
+}
+
+impl fmt::Display for Dimensions<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header = self.header;
+        let (width, height, depth) = header.level_dimensions(self.level);
+        write!(f, "{width}")?;
+        if header.pixel_height > 0 || header.pixel_depth > 0 {
+            write!(f, "x{height}")?;
+        }
+        if header.pixel_depth > 0 {
+            write!(f, "x{depth}")?;
         }
+        Ok(())
     }
 }
 
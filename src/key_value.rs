@@ -0,0 +1,261 @@
+//! KTX key/value metadata.
+use crate::header::read_u32;
+use core::{fmt, str::FromStr};
+
+/// Key for texture orientation metadata, see [`Orientation`](struct.Orientation.html).
+pub const KTX_ORIENTATION: &str = "KTXorientation";
+/// Key for texture channel swizzle metadata, see [`parse_swizzle`](fn.parse_swizzle.html).
+pub const KTX_SWIZZLE: &str = "KTXswizzle";
+/// Key for texture data hash metadata, a NUL terminated `blake3:<hex digest>` string.
+///
+/// Not a standard KTX key, see
+/// [`KtxBuilder::content_hash`](../write/struct.KtxBuilder.html#method.content_hash).
+pub const KTX_HASH: &str = "KTXhash";
+
+/// Iterator over key/value pairs, yielding `(key, value)`.
+///
+/// Keys are UTF-8 strings with the NUL terminator removed. Values are raw bytes, so
+/// string values include their NUL terminator. Iteration ends at the first malformed pair.
+#[derive(Debug, Clone)]
+pub struct KeyValues<'a> {
+    data: &'a [u8],
+    big_endian: bool,
+}
+
+impl<'a> KeyValues<'a> {
+    pub(crate) fn new(data: &'a [u8], big_endian: bool) -> Self {
+        Self { data, big_endian }
+    }
+
+    #[cfg(feature = "std")]
+    /// Returns `true` if all of the data parses as key/value pairs, i.e. iteration
+    /// does not end early at a malformed pair.
+    pub(crate) fn is_well_formed(&self) -> bool {
+        let mut pairs = self.clone();
+        while !pairs.data.is_empty() {
+            if pairs.next().is_none() {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+impl<'a> Iterator for KeyValues<'a> {
+    type Item = (&'a str, &'a [u8]);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.data.len() < 4 {
+            return None;
+        }
+        let len = read_u32(self.data, 0, self.big_endian) as usize;
+        let pair = self.data.get(4..4usize.checked_add(len)?);
+        let parsed = pair.and_then(|pair| {
+            let nul = pair.iter().position(|b| *b == 0)?;
+            let key = core::str::from_utf8(&pair[..nul]).ok()?;
+            Some((key, &pair[nul + 1..]))
+        });
+        match parsed {
+            Some(pair) => {
+                let next = (4 + len + 3) & !3;
+                self.data = self.data.get(next..).unwrap_or(&[]);
+                Some(pair)
+            }
+            None => {
+                self.data = &[];
+                None
+            }
+        }
+    }
+}
+
+impl core::iter::FusedIterator for KeyValues<'_> {}
+
+/// Texture orientation, the direction texture coordinates increase in.
+///
+/// Stored as `KTXorientation` metadata, e.g. `"S=r,T=d"`.
+///
+/// # Example
+/// ```
+/// use ktx::key_value::{Direction, Orientation};
+///
+/// let orientation: Orientation = "S=r,T=u".parse().unwrap();
+/// assert_eq!(orientation.t, Direction::Up);
+/// assert_eq!(orientation.to_string(), "S=r,T=u");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Orientation {
+    /// Direction of the `s` axis, [`Right`](enum.Direction.html#variant.Right) or
+    /// [`Left`](enum.Direction.html#variant.Left).
+    pub s: Direction,
+    /// Direction of the `t` axis, [`Down`](enum.Direction.html#variant.Down) or
+    /// [`Up`](enum.Direction.html#variant.Up). Textures with `t` increasing `Up` should
+    /// have the V texture coordinate flipped when rendered with a top-left origin.
+    pub t: Direction,
+    /// Direction of the `r` axis for 3D textures, [`In`](enum.Direction.html#variant.In) or
+    /// [`Out`](enum.Direction.html#variant.Out).
+    pub r: Option<Direction>,
+}
+
+/// Direction a texture axis increases in, see [`Orientation`](struct.Orientation.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Direction {
+    Right,
+    Left,
+    Down,
+    Up,
+    In,
+    Out,
+}
+
+impl Direction {
+    fn char(self) -> char {
+        match self {
+            Self::Right => 'r',
+            Self::Left => 'l',
+            Self::Down => 'd',
+            Self::Up => 'u',
+            Self::In => 'i',
+            Self::Out => 'o',
+        }
+    }
+}
+
+impl Orientation {
+    /// Parses a `KTXorientation` value, with or without a NUL terminator.
+    pub fn from_value(value: &[u8]) -> Option<Self> {
+        let value = value.strip_suffix(&[0]).unwrap_or(value);
+        core::str::from_utf8(value).ok()?.parse().ok()
+    }
+}
+
+impl FromStr for Orientation {
+    type Err = InvalidOrientation;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut axes = s.split(',');
+        let s = parse_axis(axes.next(), "S=", Direction::Right, Direction::Left)?;
+        let t = parse_axis(axes.next(), "T=", Direction::Down, Direction::Up)?;
+        let r = match axes.next() {
+            Some(r) => Some(parse_axis(Some(r), "R=", Direction::In, Direction::Out)?),
+            None => None,
+        };
+        if axes.next().is_some() {
+            return Err(InvalidOrientation);
+        }
+        Ok(Self { s, t, r })
+    }
+}
+
+/// Parses an axis direction like `"S=r"`, allowing either direction `a` or `b`.
+fn parse_axis(
+    axis: Option<&str>,
+    name: &str,
+    a: Direction,
+    b: Direction,
+) -> Result<Direction, InvalidOrientation> {
+    let dir = axis
+        .and_then(|axis| axis.strip_prefix(name))
+        .ok_or(InvalidOrientation)?;
+    [a, b]
+        .iter()
+        .copied()
+        .find(|d| dir.len() == 1 && dir.starts_with(d.char()))
+        .ok_or(InvalidOrientation)
+}
+
+impl fmt::Display for Orientation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "S={},T={}", self.s.char(), self.t.char())?;
+        if let Some(r) = self.r {
+            write!(f, ",R={}", r.char())?;
+        }
+        Ok(())
+    }
+}
+
+/// Source of a texture channel when sampled, see [`parse_swizzle`](fn.parse_swizzle.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Channel {
+    Red,
+    Green,
+    Blue,
+    Alpha,
+    /// Constant `0`.
+    Zero,
+    /// Constant `1`.
+    One,
+}
+
+impl Channel {
+    /// Returns the `KTXswizzle` character, e.g. `b'r'` for `Red`.
+    pub fn to_byte(self) -> u8 {
+        match self {
+            Self::Red => b'r',
+            Self::Green => b'g',
+            Self::Blue => b'b',
+            Self::Alpha => b'a',
+            Self::Zero => b'0',
+            Self::One => b'1',
+        }
+    }
+
+    fn from_byte(b: u8) -> Option<Self> {
+        Some(match b {
+            b'r' => Self::Red,
+            b'g' => Self::Green,
+            b'b' => Self::Blue,
+            b'a' => Self::Alpha,
+            b'0' => Self::Zero,
+            b'1' => Self::One,
+            _ => return None,
+        })
+    }
+}
+
+/// Parses a `KTXswizzle` value, with or without a NUL terminator, into the source of
+/// each of the red, green, blue & alpha channels.
+///
+/// # Example
+/// ```
+/// use ktx::key_value::{parse_swizzle, Channel::*};
+///
+/// assert_eq!(parse_swizzle(b"rrr1\0"), Some([Red, Red, Red, One]));
+/// ```
+pub fn parse_swizzle(value: &[u8]) -> Option<[Channel; 4]> {
+    match value.strip_suffix(&[0]).unwrap_or(value) {
+        &[r, g, b, a] => Some([
+            Channel::from_byte(r)?,
+            Channel::from_byte(g)?,
+            Channel::from_byte(b)?,
+            Channel::from_byte(a)?,
+        ]),
+        _ => None,
+    }
+}
+
+/// Error parsing an [`Orientation`](struct.Orientation.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InvalidOrientation;
+
+impl fmt::Display for InvalidOrientation {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("invalid KTXorientation value")
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for InvalidOrientation {}
+
+/// Returns the `KTXhash` value for the hash.
+#[cfg(feature = "hash")]
+pub(crate) fn hash_value(hash: &blake3::Hash) -> String {
+    format!("blake3:{}\0", hash.to_hex())
+}
+
+/// Parses a `KTXhash` value.
+#[cfg(feature = "hash")]
+pub(crate) fn parse_hash(value: &[u8]) -> Option<blake3::Hash> {
+    let value = value.strip_suffix(&[0])?.strip_prefix(b"blake3:")?;
+    blake3::Hash::from_hex(value).ok()
+}
@@ -28,12 +28,42 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::cast_lossless)]
 
+#[cfg(feature = "async")]
+pub mod async_read;
+#[cfg(feature = "bytes")]
+pub mod buf;
+#[cfg(feature = "capi")]
+pub mod capi;
+#[cfg(feature = "std")]
+pub mod convert;
+#[cfg(feature = "std")]
+pub mod edit;
+mod error;
+pub mod format;
+#[cfg(feature = "gl")]
+pub mod gl_upload;
 pub mod header;
+pub mod key_value;
+#[cfg(feature = "std")]
+pub mod lint;
 #[cfg(feature = "std")]
 pub mod read;
 pub mod slice;
+#[cfg(feature = "ash")]
+pub mod vulkan;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+#[cfg(feature = "std")]
+pub mod write;
 
+#[cfg(feature = "async")]
+pub use async_read::AsyncDecoder;
+pub use error::{HeaderError, KtxError};
 pub use header::KtxInfo;
 #[cfg(feature = "std")]
+pub use lint::lint;
+#[cfg(feature = "std")]
 pub use read::KtxDecoder as Decoder;
 pub use slice::Ktx;
+#[cfg(feature = "std")]
+pub use write::{from_r8, from_rgb8, from_rgba8, KtxBuilder, KtxEncoder as Encoder};
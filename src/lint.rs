@@ -0,0 +1,144 @@
+//! Diagnostics for questionable, but parseable, KTX data.
+//!
+//! # Example
+//! ```
+//! use ktx::lint::{Diagnostic, Severity};
+//!
+//! let image = ktx::include_ktx!("../tests/babg-bc3.ktx");
+//! let diagnostics = ktx::lint(&image);
+//! assert_eq!(diagnostics, [Diagnostic::MissingOrientation]);
+//! assert_eq!(diagnostics[0].severity(), Severity::Info);
+//! ```
+use crate::{format, header::read_u32, key_value::KTX_ORIENTATION, slice::Ktx, KtxInfo};
+use std::fmt;
+
+/// How actionable a [`Diagnostic`](enum.Diagnostic.html) is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Valid data that some consumers may handle poorly.
+    Info,
+    /// Data likely to be rejected or misinterpreted by common loaders.
+    Warning,
+}
+
+/// A lint finding, see [`lint`](fn.lint.html).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Diagnostic {
+    /// Non-power-of-two dimensions with a full mipmap chain, which GLES 2 / WebGL 1
+    /// cannot sample.
+    NonPowerOfTwoMipmaps { width: u32, height: u32 },
+    /// `mipmap_levels` is `0`, requesting generation at load time, for a compressed format.
+    /// Loaders generally cannot generate compressed mipmaps.
+    GeneratedCompressedMipmaps,
+    /// No `KTXorientation` metadata, so loaders must assume an orientation.
+    MissingOrientation,
+    /// Non-array cubemap level 0 `imageSize` is the size of the whole level, all 6 faces,
+    /// rather than the size of a single face as the spec requires. Readers applying the
+    /// cubemap &times;6 rule will misread the data.
+    CubemapLevelImageSize,
+    /// Level data length does not match the length expected from the header dimensions & format.
+    LevelSize {
+        level: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// Header dimensions, `faces` & `array_elements` give a level length too large to
+    /// address, so level sizes cannot be checked.
+    DimensionsOverflow,
+}
+
+impl Diagnostic {
+    /// Returns the severity of the diagnostic.
+    pub fn severity(&self) -> Severity {
+        match self {
+            Self::MissingOrientation => Severity::Info,
+            Self::NonPowerOfTwoMipmaps { .. }
+            | Self::GeneratedCompressedMipmaps
+            | Self::CubemapLevelImageSize
+            | Self::LevelSize { .. }
+            | Self::DimensionsOverflow => Severity::Warning,
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::NonPowerOfTwoMipmaps { width, height } => write!(
+                f,
+                "non-power-of-two {width}x{height} texture has a full mipmap chain"
+            ),
+            Self::GeneratedCompressedMipmaps => {
+                f.write_str("mipmap generation requested for a compressed format")
+            }
+            Self::MissingOrientation => f.write_str("missing KTXorientation metadata"),
+            Self::CubemapLevelImageSize => {
+                f.write_str("cubemap imageSize is the size of all faces, not a single face")
+            }
+            Self::LevelSize {
+                level,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected texture level {level} to be {expected} bytes, got {actual}"
+            ),
+            Self::DimensionsOverflow => f.write_str("header dimensions overflow"),
+        }
+    }
+}
+
+/// Returns diagnostics for questionable, but parseable, KTX data.
+///
+/// Missing level data is not reported, see [`Ktx::try_textures`](../struct.Ktx.html#method.try_textures).
+pub fn lint<D: AsRef<[u8]>>(ktx: &Ktx<D>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+
+    let (width, height, depth) = (ktx.pixel_width(), ktx.pixel_height(), ktx.pixel_depth());
+    let full_chain = ktx.level_count_for_full_chain();
+    let npot = [width, height, depth]
+        .iter()
+        .any(|&d| d > 0 && !d.is_power_of_two());
+    if npot && (ktx.mipmap_levels() == 0 || ktx.mipmap_levels() >= full_chain) {
+        diagnostics.push(Diagnostic::NonPowerOfTwoMipmaps { width, height });
+    }
+
+    if ktx.mipmap_levels() == 0 && ktx.is_compressed() {
+        diagnostics.push(Diagnostic::GeneratedCompressedMipmaps);
+    }
+
+    if ktx.key_value(KTX_ORIENTATION).is_none() {
+        diagnostics.push(Diagnostic::MissingOrientation);
+    }
+
+    if format::expected_level_len(ktx, 0).is_err() {
+        diagnostics.push(Diagnostic::DimensionsOverflow);
+    }
+
+    if ktx.is_cubemap() && !ktx.is_array() {
+        let start = 64 + ktx.bytes_of_key_value_data() as usize;
+        let image_size = ktx
+            .data()
+            .get(start..start + 4)
+            .map(|size| read_u32(size, 0, ktx.big_endian()) as usize);
+        if image_size.is_some() && image_size == format::expected_level_len(ktx, 0).unwrap_or(None)
+        {
+            diagnostics.push(Diagnostic::CubemapLevelImageSize);
+        }
+    }
+
+    for (level, data) in ktx.try_textures().map_while(Result::ok).enumerate() {
+        let level = level as u32;
+        if let Ok(Some(expected)) = format::expected_level_len(ktx, level) {
+            if data.len() != expected {
+                diagnostics.push(Diagnostic::LevelSize {
+                    level,
+                    expected,
+                    actual: data.len(),
+                });
+            }
+        }
+    }
+
+    diagnostics
+}
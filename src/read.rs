@@ -1,8 +1,13 @@
-use crate::header::*;
+use crate::{
+    error::KtxError,
+    format::{expected_level_len, level_data_len, padding_len, split_level_len},
+    header::*,
+};
 use byteorder::{BigEndian, ByteOrder, LittleEndian};
 use std::{
+    error::Error,
     fmt,
-    io::{self, Read},
+    io::{self, Read, SeekFrom},
 };
 
 /// KTX texture storage format reader. Useful when reading from a file and/or compressed data.
@@ -24,6 +29,9 @@ use std::{
 pub struct KtxDecoder<R> {
     header: KtxHeader,
     data: R,
+    /// Stream position at the end of the header, recorded by the first
+    /// [`textures`](#method.textures) call.
+    header_end: Option<u64>,
 }
 
 impl<R> AsRef<KtxHeader> for KtxDecoder<R> {
@@ -43,12 +51,20 @@ impl<R> fmt::Debug for KtxDecoder<R> {
 
 impl<R: io::Read> KtxDecoder<R> {
     /// Reads KTX header data and returns a `KtxDecoder`.
+    ///
+    /// Returns an `InvalidData` error wrapping a [`HeaderError`](../enum.HeaderError.html)
+    /// if the header is invalid.
     #[inline]
     pub fn new(mut data: R) -> io::Result<Self> {
         let mut header_data = [0; 64];
         data.read_exact(&mut header_data)?;
-        let header = KtxHeader::new(&header_data);
-        Ok(Self { header, data })
+        let header = KtxHeader::try_from_bytes(&header_data)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        Ok(Self {
+            header,
+            data,
+            header_end: None,
+        })
     }
 
     /// Consumes the `KtxDecoder` to returns an iterator reading texture levels starting at level 0.
@@ -58,9 +74,163 @@ impl<R: io::Read> KtxDecoder<R> {
             header: self.header,
             data: self.data,
             next_level: 0,
+            padding: 0,
+        }
+    }
+
+    /// Consumes the `KtxDecoder` to returns an iterator reading texture levels starting at
+    /// level 0, into buffers taken from `pool`.
+    ///
+    /// `pool` is called with the length of each level, returning a `Vec` to be cleared &
+    /// filled with the level data. This allows buffers to be recycled across levels & files
+    /// instead of allocating a new `Vec` for each level.
+    ///
+    /// Iteration ends after yielding an error, e.g. `UnexpectedEof` for truncated data.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// let mut pool: Vec<Vec<u8>> = Vec::new();
+    /// for _ in 0..3 {
+    ///     # let buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    ///     let decoder = ktx::Decoder::new(buf_reader)?;
+    ///     let texture_levels: Vec<Vec<u8>> = decoder
+    ///         .read_textures_with(|_len| pool.pop().unwrap_or_default())
+    ///         .collect::<Result<_, _>>()?;
+    ///     // process levels, then recycle the buffers
+    ///     pool.extend(texture_levels);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn read_textures_with<P>(self, pool: P) -> PooledTextures<R, P>
+    where
+        P: FnMut(usize) -> Vec<u8>,
+    {
+        PooledTextures {
+            textures: self.read_textures(),
+            pool,
+        }
+    }
+
+    /// Consumes the `KtxDecoder` to returns an iterator strictly reading texture levels
+    /// starting at level 0.
+    ///
+    /// Unlike [`read_textures`](#method.read_textures) errors are yielded, after which
+    /// iteration ends:
+    /// * Each `imageSize` must match the size computed from the header, for known formats.
+    /// * Data ending before a level is complete yields
+    ///   [`KtxReadError::UnexpectedEof`](enum.KtxReadError.html#variant.UnexpectedEof).
+    /// * After the last level the data must end, otherwise
+    ///   [`KtxReadError::TrailingData`](enum.KtxReadError.html#variant.TrailingData) is yielded.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    /// let decoder = ktx::Decoder::new(buf_reader)?;
+    /// let texture_levels: Vec<Vec<u8>> = decoder.read_textures_strict().collect::<Result<_, _>>()?;
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn read_textures_strict(self) -> StrictTextures<R> {
+        StrictTextures {
+            textures: self.read_textures(),
+            done: false,
         }
     }
 
+    /// Consumes the `KtxDecoder` to returns an iterator reading each face of each texture level
+    /// starting at level 0, yielding `(level, element_index, face_index, data)`.
+    ///
+    /// Non-cubemap textures yield a single face per level. For array textures each array
+    /// element's faces are yielded in turn. Non-array textures have a single element `0`.
+    ///
+    /// Iteration ends after yielding an error, e.g. `UnexpectedEof` for truncated data or
+    /// `InvalidData`, wrapping [`KtxError::UnevenLevel`](../enum.KtxError.html#variant.UnevenLevel),
+    /// if a level cannot be split into equal faces.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/uffizi_rgba16f_cube.ktx")?);
+    /// let decoder = ktx::Decoder::new(buf_reader)?;
+    /// for face in decoder.read_faces() {
+    ///     let (level, element_index, face_index, data) = face?;
+    ///     // +X, -X, +Y, -Y, +Z, -Z faces for each level
+    ///     # let _ = (level, element_index, face_index, data);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn read_faces(self) -> Faces<R> {
+        Faces {
+            textures: self.read_textures(),
+            face_len: 0,
+            level_faces_remaining: 0,
+            next_face: 0,
+        }
+    }
+
+    /// Consumes the `KtxDecoder` to returns an iterator reading each array element of each
+    /// texture level starting at level 0, yielding `(level, element_index, data)`.
+    ///
+    /// Each element contains all of its faces. Non-array textures yield a single element per level.
+    ///
+    /// Iteration ends after yielding an error, as with [`read_faces`](#method.read_faces).
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    /// let decoder = ktx::Decoder::new(buf_reader)?;
+    /// for element in decoder.read_elements() {
+    ///     let (level, element_index, data) = element?;
+    ///     // stream individual array layers
+    ///     # let _ = (level, element_index, data);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    #[inline]
+    pub fn read_elements(self) -> Elements<R> {
+        Elements {
+            textures: self.read_textures(),
+            element_len: 0,
+            level_elements_remaining: 0,
+            next_element: 0,
+        }
+    }
+
+    /// Consumes the `KtxDecoder` to read a single texture level, starting at `0`.
+    /// Earlier levels are read & discarded without allocating.
+    ///
+    /// Errors if the level is >= the `mipmap_levels` value (or `1` when `mipmap_levels` is `0`),
+    /// or if reading fails, e.g. with `UnexpectedEof` for truncated data.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    /// let decoder = ktx::Decoder::new(buf_reader)?;
+    /// let level_2: Vec<u8> = decoder.nth_texture(2)?;
+    /// # Ok(()) }
+    /// ```
+    pub fn nth_texture(self, level: u32) -> io::Result<Vec<u8>> {
+        if level >= self.header.mipmap_levels().max(1) {
+            return Err(io::Error::new(io::ErrorKind::InvalidInput, "invalid level"));
+        }
+        let mut textures = self.read_textures();
+        for _ in 0..level {
+            textures.skip_level()?;
+        }
+        textures.read_level()
+    }
+
     /// Returns `KtxHeader`. Useful if this info is desired after consuming the `KtxDecoder`.
     ///
     /// # Example
@@ -78,6 +248,176 @@ impl<R: io::Read> KtxDecoder<R> {
     }
 }
 
+impl<R: io::BufRead> KtxDecoder<R> {
+    /// Consumes the `KtxDecoder` to read all texture levels starting at level 0, calling `f`
+    /// with `(level, chunk)` for each chunk of level data.
+    ///
+    /// Chunks are borrowed directly from the reader's internal buffer, so level data is never
+    /// copied into an allocated `Vec`. Each level is passed in one or more chunks, in order.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    /// let decoder = ktx::Decoder::new(buf_reader)?;
+    /// let mut level_0_len = 0;
+    /// decoder.for_each_level_chunk(|level, chunk| {
+    ///     // e.g. write the chunk to a mapped GPU staging buffer
+    ///     if level == 0 {
+    ///         level_0_len += chunk.len();
+    ///     }
+    /// })?;
+    /// assert_eq!(level_0_len, 52000);
+    /// # Ok(()) }
+    /// ```
+    pub fn for_each_level_chunk<F>(self, mut f: F) -> io::Result<()>
+    where
+        F: FnMut(u32, &[u8]),
+    {
+        let levels = self.header.mipmap_levels().max(1);
+        let mut textures = self.read_textures();
+        for level in 0..levels {
+            let level_len = textures.next_level_len()?;
+            let mut remaining = level_len;
+            while remaining > 0 {
+                let chunk = textures.data.fill_buf()?;
+                if chunk.is_empty() {
+                    return Err(io::ErrorKind::UnexpectedEof.into());
+                }
+                let chunk = &chunk[..chunk.len().min(remaining as usize)];
+                let chunk_len = chunk.len();
+                f(level, chunk);
+                textures.data.consume(chunk_len);
+                remaining -= chunk_len as u64;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<R: io::Read + io::Seek> KtxDecoder<R> {
+    /// Returns an iterator reading texture levels starting at level 0, borrowing the reader,
+    /// yielding `io::Result<Vec<u8>>`.
+    ///
+    /// Each call rewinds the reader to the start of the texture data, so levels can be read
+    /// multiple times, e.g. a checksum pass followed by an upload pass. Iteration ends after
+    /// yielding an error, e.g. `UnexpectedEof` for truncated data.
+    ///
+    /// Note consuming methods, like [`read_textures`](#method.read_textures), do not rewind
+    /// so should not be used after this method.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    /// let mut decoder = ktx::Decoder::new(buf_reader)?;
+    /// let total_len = decoder
+    ///     .textures()?
+    ///     .map(|level| level.map(|level| level.len()))
+    ///     .sum::<std::io::Result<usize>>()?;
+    /// let texture_levels = decoder.textures()?.collect::<std::io::Result<Vec<_>>>()?;
+    /// assert_eq!(texture_levels.iter().map(Vec::len).sum::<usize>(), total_len);
+    /// # Ok(()) }
+    /// ```
+    pub fn textures(&mut self) -> io::Result<TryTextures<&mut R>> {
+        match self.header_end {
+            Some(header_end) => {
+                self.data.seek(SeekFrom::Start(header_end))?;
+            }
+            None => self.header_end = Some(self.data.stream_position()?),
+        }
+        Ok(TryTextures {
+            textures: Textures {
+                header: self.header,
+                data: &mut self.data,
+                next_level: 0,
+                padding: 0,
+            },
+        })
+    }
+
+    /// Consumes the `KtxDecoder` to returns an iterator reading texture levels smallest first,
+    /// starting at the last level, yielding `(level, data)`.
+    ///
+    /// Iteration ends after yielding an error, e.g. `UnexpectedEof` for truncated level data.
+    ///
+    /// Level offsets are indexed upfront by seeking past the level data, so a low resolution
+    /// level can be displayed before larger levels are read.
+    ///
+    /// # Example
+    /// ```
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// # let mut buf_reader = BufReader::new(File::open("tests/babg-bc3.ktx")?);
+    /// let decoder = ktx::Decoder::new(buf_reader)?;
+    /// for level in decoder.read_textures_reversed()? {
+    ///     let (level, data) = level?;
+    ///     // display the level, refining as larger levels arrive
+    ///     # let _ = (level, data);
+    /// }
+    /// # Ok(()) }
+    /// ```
+    pub fn read_textures_reversed(self) -> io::Result<ReversedTextures<R>> {
+        let levels = self.header.mipmap_levels().max(1);
+        // mipmap_levels is unchecked, so limit preallocation
+        let mut index = Vec::with_capacity(levels.min(self.level_count_for_full_chain()) as _);
+        let mut textures = self.read_textures();
+        for _ in 0..levels {
+            let level_len = textures.next_level_len()?;
+            let start = textures.data.stream_position()?;
+            index.push((start, level_len));
+            // skip level data, mipPadding is skipped reading the next level length
+            textures.data.seek(SeekFrom::Current(level_len as _))?;
+        }
+        Ok(ReversedTextures {
+            data: textures.data,
+            index,
+        })
+    }
+}
+
+#[cfg(feature = "gzip")]
+impl<R: io::Read> KtxDecoder<flate2::read::GzDecoder<R>> {
+    /// Reads gzip compressed KTX data, e.g. a `.ktx.gz` file, decompressing as texture
+    /// levels are read.
+    ///
+    /// Requires the `gzip` feature.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// let decoder = ktx::Decoder::new_gzip(BufReader::new(File::open("logo.ktx.gz")?))?;
+    /// let texture_levels: Vec<Vec<u8>> = decoder.read_textures().collect();
+    /// # Ok(()) }
+    /// ```
+    pub fn new_gzip(data: R) -> io::Result<Self> {
+        Self::new(flate2::read::GzDecoder::new(data))
+    }
+}
+
+#[cfg(feature = "zstd")]
+impl<R: io::BufRead> KtxDecoder<zstd::stream::read::Decoder<'static, R>> {
+    /// Reads zstd compressed KTX data, e.g. a `.ktx.zst` file, decompressing as texture
+    /// levels are read.
+    ///
+    /// Requires the `zstd` feature.
+    ///
+    /// # Example
+    /// ```no_run
+    /// # use std::{io::BufReader, fs::File};
+    /// # fn main() -> std::io::Result<()> {
+    /// let decoder = ktx::Decoder::new_zstd(BufReader::new(File::open("logo.ktx.zst")?))?;
+    /// let texture_levels: Vec<Vec<u8>> = decoder.read_textures().collect();
+    /// # Ok(()) }
+    /// ```
+    pub fn new_zstd(data: R) -> io::Result<Self> {
+        Self::new(zstd::stream::read::Decoder::with_buffer(data)?)
+    }
+}
+
 /// Iterator that reads texture level data into `Vec<u8>`.
 ///
 /// For cubemap textures each level will contain all 6 faces
@@ -87,50 +427,466 @@ pub struct Textures<R> {
     header: KtxHeader,
     data: R,
     next_level: u32,
+    /// `mipPadding` length after the previous level, skipped before the next level.
+    padding: u64,
+}
+
+impl<R: io::Read> Textures<R> {
+    /// Skips key-value data before level 0, or the previous level's `mipPadding`, & reads
+    /// the length of the next level.
+    fn next_level_len(&mut self) -> io::Result<u64> {
+        // skip key-value data
+        if self.next_level == 0 && self.header.bytes_of_key_value_data() != 0 {
+            let key_value_len = self.header.bytes_of_key_value_data() as _;
+            io::copy(&mut self.data.by_ref().take(key_value_len), &mut io::sink())?;
+        }
+        if self.padding > 0 {
+            io::copy(&mut self.data.by_ref().take(self.padding), &mut io::sink())?;
+            self.padding = 0;
+        }
+
+        self.next_level += 1;
+        let image_size = {
+            let mut len = [0; 4];
+            self.data.read_exact(&mut len)?;
+            if self.header.big_endian() {
+                BigEndian::read_u32(&len)
+            } else {
+                LittleEndian::read_u32(&len)
+            }
+        };
+        let level_len = level_data_len(image_size, &self.header);
+        self.padding = padding_len(level_len);
+        Ok(level_len)
+    }
+
+    /// Reads the next level into a new `Vec`.
+    fn read_level(&mut self) -> io::Result<Vec<u8>> {
+        self.read_level_with(|_| Vec::new())
+    }
+
+    /// Reads the next level into a `Vec` returned by `pool` for the level length.
+    fn read_level_with(&mut self, pool: impl FnOnce(usize) -> Vec<u8>) -> io::Result<Vec<u8>> {
+        let level_len = self.next_level_len()?;
+        let mut level = pool(level_len as _);
+        level.clear();
+        read_exact_len(&mut self.data, level_len, &mut level)?;
+        Ok(level)
+    }
+
+    /// Reads & discards the next level.
+    fn skip_level(&mut self) -> io::Result<()> {
+        let level_len = self.next_level_len()?;
+        let skipped = io::copy(&mut self.data.by_ref().take(level_len), &mut io::sink())?;
+        if skipped != level_len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(())
+    }
+
+    /// Ends iteration, e.g. after an error.
+    #[inline]
+    fn end(&mut self) {
+        self.next_level = self.header.mipmap_levels().max(1);
+    }
+}
+
+/// Maximum bytes reserved for level data before reading, as `imageSize` values are
+/// untrusted. Larger levels grow the buffer as data is actually read.
+pub(crate) const MAX_PREALLOCATE: u64 = 1 << 20;
+
+fn invalid_data(err: KtxError) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, err)
+}
+
+/// Reads exactly `len` bytes, appending to `buf`, erroring with `UnexpectedEof` if the
+/// data ends first.
+fn read_exact_len(data: &mut impl Read, len: u64, buf: &mut Vec<u8>) -> io::Result<()> {
+    buf.reserve(len.min(MAX_PREALLOCATE) as usize);
+    let read = data.take(len).read_to_end(buf)?;
+    if read as u64 != len {
+        return Err(io::ErrorKind::UnexpectedEof.into());
+    }
+    Ok(())
 }
 
 impl<R: io::Read> Iterator for Textures<R> {
     type Item = Vec<u8>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next_level >= self.header.mipmap_levels() {
+        if self.next_level >= self.header.mipmap_levels().max(1) {
             None
         } else {
-            // skip key-value data
-            if self.next_level == 0 && self.header.bytes_of_key_value_data() != 0 {
-                let mut discard = Vec::with_capacity(self.header.bytes_of_key_value_data() as _);
-                self.data
-                    .by_ref()
-                    .take(self.header.bytes_of_key_value_data() as _)
-                    .read_to_end(&mut discard)
-                    .ok()?;
+            let level = self.read_level();
+            if level.is_err() {
+                self.end();
             }
+            level.ok()
+        }
+    }
+}
 
-            self.next_level += 1;
-            let mut level_len = {
-                let mut len = [0; 4];
-                self.data.read_exact(&mut len).ok()?;
-                if self.header.big_endian() {
-                    BigEndian::read_u32(&len)
-                } else {
-                    LittleEndian::read_u32(&len)
-                }
-            };
+impl<R: io::Read> std::iter::FusedIterator for Textures<R> {}
 
-            if self.header.array_elements() == 0 && self.header.faces() == 6 {
-                // Multiply for each face, see https://www.khronos.org/registry/KTX/specs/1.0/ktxspec_v1.html#2.16
-                level_len *= 6;
+/// Iterator that reads texture level data into `Vec<u8>`, yielding `io::Result<Vec<u8>>`.
+/// See [`KtxDecoder::textures`](struct.KtxDecoder.html#method.textures).
+#[derive(Debug)]
+pub struct TryTextures<R> {
+    textures: Textures<R>,
+}
+
+impl<R: io::Read> Iterator for TryTextures<R> {
+    type Item = io::Result<Vec<u8>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.textures.next_level >= self.textures.header.mipmap_levels().max(1) {
+            None
+        } else {
+            let level = self.textures.read_level();
+            if level.is_err() {
+                self.textures.end();
             }
+            Some(level)
+        }
+    }
+}
+
+impl<R: io::Read> std::iter::FusedIterator for TryTextures<R> {}
+
+/// Iterator that reads texture level data into `Vec<u8>` buffers taken from a pool,
+/// yielding `io::Result<Vec<u8>>`. See [`KtxDecoder::read_textures_with`](struct.KtxDecoder.html#method.read_textures_with).
+pub struct PooledTextures<R, P> {
+    textures: Textures<R>,
+    pool: P,
+}
+
+impl<R, P> fmt::Debug for PooledTextures<R, P>
+where
+    R: fmt::Debug,
+{
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.debug_struct("PooledTextures")
+            .field("textures", &self.textures)
+            .finish()
+    }
+}
+
+impl<R, P> Iterator for PooledTextures<R, P>
+where
+    R: io::Read,
+    P: FnMut(usize) -> Vec<u8>,
+{
+    type Item = io::Result<Vec<u8>>;
 
-            let mut level = Vec::with_capacity(level_len as _);
-            self.data
-                .by_ref()
-                .take(level_len as _)
-                .read_to_end(&mut level)
-                .ok()?;
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.textures.next_level >= self.textures.header.mipmap_levels().max(1) {
+            None
+        } else {
+            let level = self.textures.read_level_with(&mut self.pool);
+            if level.is_err() {
+                self.textures.end();
+            }
             Some(level)
         }
     }
 }
 
-impl<R: io::Read> std::iter::FusedIterator for Textures<R> {}
+impl<R, P> std::iter::FusedIterator for PooledTextures<R, P>
+where
+    R: io::Read,
+    P: FnMut(usize) -> Vec<u8>,
+{
+}
+
+/// Iterator that reads texture level data into `Vec<u8>` smallest first, yielding
+/// `io::Result<(level, data)>`. See
+/// [`KtxDecoder::read_textures_reversed`](struct.KtxDecoder.html#method.read_textures_reversed).
+#[derive(Debug)]
+pub struct ReversedTextures<R> {
+    data: R,
+    /// `(data offset, length)` of each level.
+    index: Vec<(u64, u64)>,
+}
+
+impl<R: io::Read + io::Seek> Iterator for ReversedTextures<R> {
+    type Item = io::Result<(u32, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (start, len) = self.index.pop()?;
+        let level = self.index.len() as u32;
+
+        let read = |data: &mut R| -> io::Result<Vec<u8>> {
+            data.seek(SeekFrom::Start(start))?;
+            let mut level_data = Vec::new();
+            read_exact_len(data, len, &mut level_data)?;
+            Ok(level_data)
+        };
+        let result = read(&mut self.data);
+        if result.is_err() {
+            self.index.clear();
+        }
+        Some(result.map(|level_data| (level, level_data)))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.index.len(), Some(self.index.len()))
+    }
+}
+
+impl<R: io::Read + io::Seek> ExactSizeIterator for ReversedTextures<R> {}
+
+impl<R: io::Read + io::Seek> std::iter::FusedIterator for ReversedTextures<R> {}
+
+/// Iterator that reads each texture level face into `Vec<u8>`, yielding
+/// `io::Result<(level, element_index, face_index, data)>`.
+///
+/// For cubemap textures faces are in order: +X, -X, +Y, -Y, +Z, -Z.
+#[derive(Debug)]
+pub struct Faces<R> {
+    textures: Textures<R>,
+    face_len: u64,
+    level_faces_remaining: u64,
+    /// Index of the next face within the level, across all array elements.
+    next_face: u64,
+}
+
+impl<R: io::Read> Faces<R> {
+    fn read_face(&mut self) -> io::Result<(u32, u32, u32, Vec<u8>)> {
+        let header = self.textures.header;
+        let faces = header.faces().max(1) as u64;
+
+        if self.level_faces_remaining == 0 {
+            let level_len = self.textures.next_level_len()?;
+            let level_faces = faces * header.array_elements().max(1) as u64;
+            let level = self.textures.next_level - 1;
+            self.face_len = split_level_len(level, level_len, level_faces).map_err(invalid_data)?;
+            self.level_faces_remaining = level_faces;
+            self.next_face = 0;
+        }
+
+        let mut face = Vec::new();
+        read_exact_len(&mut self.textures.data, self.face_len, &mut face)?;
+
+        let element_index = (self.next_face / faces) as u32;
+        let face_index = (self.next_face % faces) as u32;
+        self.next_face += 1;
+        self.level_faces_remaining -= 1;
+        Ok((
+            self.textures.next_level - 1,
+            element_index,
+            face_index,
+            face,
+        ))
+    }
+}
+
+impl<R: io::Read> Iterator for Faces<R> {
+    type Item = io::Result<(u32, u32, u32, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.level_faces_remaining == 0
+            && self.textures.next_level >= self.textures.header.mipmap_levels().max(1)
+        {
+            return None;
+        }
+        let face = self.read_face();
+        if face.is_err() {
+            self.level_faces_remaining = 0;
+            self.textures.end();
+        }
+        Some(face)
+    }
+}
+
+impl<R: io::Read> std::iter::FusedIterator for Faces<R> {}
+
+/// Iterator that reads each texture level array element into `Vec<u8>`, yielding
+/// `io::Result<(level, element_index, data)>`. Each element contains all of its faces.
+#[derive(Debug)]
+pub struct Elements<R> {
+    textures: Textures<R>,
+    element_len: u64,
+    level_elements_remaining: u32,
+    next_element: u32,
+}
+
+impl<R: io::Read> Elements<R> {
+    fn read_element(&mut self) -> io::Result<(u32, u32, Vec<u8>)> {
+        if self.level_elements_remaining == 0 {
+            let level_len = self.textures.next_level_len()?;
+            let elements = self.textures.header.array_elements().max(1);
+            let level = self.textures.next_level - 1;
+            self.element_len =
+                split_level_len(level, level_len, elements as _).map_err(invalid_data)?;
+            self.level_elements_remaining = elements;
+            self.next_element = 0;
+        }
+
+        let mut element = Vec::new();
+        read_exact_len(&mut self.textures.data, self.element_len, &mut element)?;
+
+        let element_index = self.next_element;
+        self.next_element += 1;
+        self.level_elements_remaining -= 1;
+        Ok((self.textures.next_level - 1, element_index, element))
+    }
+}
+
+impl<R: io::Read> Iterator for Elements<R> {
+    type Item = io::Result<(u32, u32, Vec<u8>)>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.level_elements_remaining == 0
+            && self.textures.next_level >= self.textures.header.mipmap_levels().max(1)
+        {
+            return None;
+        }
+        let element = self.read_element();
+        if element.is_err() {
+            self.level_elements_remaining = 0;
+            self.textures.end();
+        }
+        Some(element)
+    }
+}
+
+impl<R: io::Read> std::iter::FusedIterator for Elements<R> {}
+
+/// Iterator that strictly reads texture level data into `Vec<u8>`, yielding errors.
+/// See [`KtxDecoder::read_textures_strict`](struct.KtxDecoder.html#method.read_textures_strict).
+#[derive(Debug)]
+pub struct StrictTextures<R> {
+    textures: Textures<R>,
+    done: bool,
+}
+
+impl<R: io::Read> StrictTextures<R> {
+    fn read_level(&mut self, level: u32) -> Result<Vec<u8>, KtxReadError> {
+        let read_err = |err: io::Error| match err.kind() {
+            io::ErrorKind::UnexpectedEof => KtxReadError::UnexpectedEof { level },
+            _ => KtxReadError::Io(err),
+        };
+
+        let level_len = self.textures.next_level_len().map_err(read_err)?;
+        let expected = expected_level_len(&self.textures.header, level)
+            .map_err(|_| KtxReadError::DimensionsOverflow)?;
+        if let Some(expected) = expected {
+            if level_len != expected as u64 {
+                return Err(KtxReadError::ImageSize {
+                    level,
+                    expected: expected as u64,
+                    actual: level_len,
+                });
+            }
+        }
+
+        // header values don't prove the data is present, so limit preallocation
+        let mut data = Vec::with_capacity(level_len.min(MAX_PREALLOCATE) as usize);
+        let data_in = &mut self.textures.data;
+        data_in
+            .take(level_len)
+            .read_to_end(&mut data)
+            .map_err(read_err)?;
+        if data.len() as u64 != level_len {
+            return Err(KtxReadError::UnexpectedEof { level });
+        }
+
+        // strictly require mipPadding, including after the last level
+        let padding = self.textures.padding as usize;
+        data_in
+            .read_exact(&mut [0; 3][..padding])
+            .map_err(read_err)?;
+        self.textures.padding = 0;
+        Ok(data)
+    }
+}
+
+impl<R: io::Read> Iterator for StrictTextures<R> {
+    type Item = Result<Vec<u8>, KtxReadError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+
+        let level = self.textures.next_level;
+        if level >= self.textures.header.mipmap_levels().max(1) {
+            self.done = true;
+            return match io::copy(&mut self.textures.data, &mut io::sink()) {
+                Ok(0) => None,
+                Ok(bytes) => Some(Err(KtxReadError::TrailingData { bytes })),
+                Err(err) => Some(Err(KtxReadError::Io(err))),
+            };
+        }
+
+        let level_data = self.read_level(level);
+        self.done = level_data.is_err();
+        Some(level_data)
+    }
+}
+
+impl<R: io::Read> std::iter::FusedIterator for StrictTextures<R> {}
+
+/// Error strictly reading KTX data, see
+/// [`KtxDecoder::read_textures_strict`](struct.KtxDecoder.html#method.read_textures_strict).
+#[derive(Debug)]
+pub enum KtxReadError {
+    /// Reading the data failed.
+    Io(io::Error),
+    /// The data ended before texture level was complete.
+    UnexpectedEof { level: u32 },
+    /// Texture level `imageSize` does not match the size computed from the header.
+    ImageSize {
+        level: u32,
+        expected: u64,
+        actual: u64,
+    },
+    /// The data continues after the last texture level.
+    TrailingData { bytes: u64 },
+    /// The header dimensions, `faces` & `array_elements` give a level length too large to
+    /// address.
+    DimensionsOverflow,
+}
+
+impl fmt::Display for KtxReadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Io(err) => err.fmt(f),
+            Self::UnexpectedEof { level } => {
+                write!(f, "data ended before texture level {level} was complete")
+            }
+            Self::ImageSize {
+                level,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected texture level {level} to be {expected} bytes, imageSize is {actual}"
+            ),
+            Self::TrailingData { bytes } => {
+                write!(
+                    f,
+                    "{bytes} bytes of trailing data after the last texture level"
+                )
+            }
+            Self::DimensionsOverflow => f.write_str("header dimensions overflow"),
+        }
+    }
+}
+
+impl Error for KtxReadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for KtxReadError {
+    #[inline]
+    fn from(err: io::Error) -> Self {
+        Self::Io(err)
+    }
+}
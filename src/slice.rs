@@ -1,6 +1,15 @@
-use crate::header::*;
-use byteorder::{BigEndian, ByteOrder, LittleEndian};
-use core::{fmt, ops::Deref};
+use crate::{
+    error::{HeaderError, KtxError},
+    format::{
+        has_face_image_size, image_rows, level_data_len, padded_len, padding_len, split_level_len,
+    },
+    header::*,
+    key_value::{self, Channel, KeyValues, Orientation, KTX_ORIENTATION, KTX_SWIZZLE},
+};
+use core::{fmt, ops::Range};
+
+/// Maximum number of texture levels, enough for a full mipmap pyramid of `u32` dimensions.
+const MAX_LEVELS: usize = 32;
 
 /// KTX texture storage format data stored in a complete slice.
 /// Provides [`KtxInfo`](../header/trait.KtxInfo.html).
@@ -17,7 +26,63 @@ use core::{fmt, ops::Deref};
 pub struct Ktx<D> {
     header: KtxHeader,
     ktx_data: D,
-    texture_start: u32,
+    levels: LevelIndex,
+}
+
+/// Texture level offsets within KTX data.
+#[derive(Debug, Clone, Copy)]
+struct LevelIndex {
+    /// Start offset of level 0 `imageSize`, ie after the key-value data.
+    texture_start: usize,
+    /// Start offsets of each texture level `imageSize` present in the data.
+    starts: [usize; MAX_LEVELS],
+    /// End offsets of each texture level present in the data, excluding `mipPadding`.
+    ends: [usize; MAX_LEVELS],
+    /// Number of valid `starts` & `ends`.
+    present: usize,
+}
+
+impl LevelIndex {
+    const fn new(header: &KtxHeader, ktx_data: &[u8]) -> Self {
+        let texture_start = 64 + header.bytes_of_key_value_data as usize;
+        let mut starts = [0; MAX_LEVELS];
+        let mut ends = [0; MAX_LEVELS];
+        let mut present = 0;
+
+        // mipmap_levels 0 indicates level 0 is stored & further levels should be generated
+        let mut levels = header.mipmap_levels as usize;
+        if levels == 0 {
+            levels = 1;
+        } else if levels > MAX_LEVELS {
+            levels = MAX_LEVELS;
+        }
+
+        let mut level_start = texture_start;
+        while present < levels && level_start.saturating_add(4) <= ktx_data.len() {
+            let image_size = read_u32(ktx_data, level_start, header.big_endian);
+            let level_len = level_data_len(image_size, header);
+            let level_len = match level_len > usize::MAX as u64 {
+                true => usize::MAX,
+                false => level_len as usize,
+            };
+            let level_end = level_start.saturating_add(4).saturating_add(level_len);
+            if level_end > ktx_data.len() {
+                break;
+            }
+            starts[present] = level_start;
+            ends[present] = level_end;
+            present += 1;
+            // skip mipPadding
+            level_start = level_end.saturating_add(padding_len(level_len as u64) as usize);
+        }
+
+        Self {
+            texture_start,
+            starts,
+            ends,
+            present,
+        }
+    }
 }
 
 impl<D> AsRef<KtxHeader> for Ktx<D> {
@@ -37,17 +102,52 @@ impl<D> fmt::Debug for Ktx<D> {
 
 impl<D> Ktx<D>
 where
-    D: Deref<Target = [u8]>,
+    D: AsRef<[u8]>,
 {
     /// Parses a complete KTX data slice and returns a `Ktx` instance.
-    #[inline]
+    ///
+    /// Texture level offsets are computed upfront so level lookups are constant-time.
+    ///
+    /// # Panics
+    ///
+    /// The data is shorter than the 64 byte header. The header is otherwise not validated,
+    /// use [`Ktx::try_new`](#method.try_new) for untrusted data.
     pub fn new(ktx_data: D) -> Self {
-        let header = KtxHeader::new(&ktx_data);
-        let texture_start = 64 + header.bytes_of_key_value_data();
+        let data = ktx_data.as_ref();
+        let header = KtxHeader::new(data);
+        Self::with_header(header, ktx_data)
+    }
+
+    /// Parses a complete KTX data slice and returns a `Ktx` instance, or a
+    /// [`HeaderError`](../enum.HeaderError.html) if the data does not start with a valid
+    /// KTX1 header.
+    ///
+    /// Never panics, so is suitable for untrusted data. Missing texture levels are
+    /// reported by [`Ktx::try_textures`](#method.try_textures).
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{HeaderError, Ktx, KtxInfo};
+    ///
+    /// let data = include_bytes!("../tests/babg-bc3.ktx");
+    /// let image = Ktx::try_new(&data[..])?;
+    /// assert_eq!(image.pixel_width(), 260);
+    ///
+    /// assert_eq!(Ktx::try_new(&data[..10]).unwrap_err(), HeaderError::TooShort);
+    /// # Ok::<(), HeaderError>(())
+    /// ```
+    pub fn try_new(ktx_data: D) -> Result<Self, HeaderError> {
+        let header = KtxHeader::try_from_prefix(ktx_data.as_ref())?;
+        Ok(Self::with_header(header, ktx_data))
+    }
+
+    #[inline]
+    fn with_header(header: KtxHeader, ktx_data: D) -> Self {
+        let levels = LevelIndex::new(&header, ktx_data.as_ref());
         Self {
             header,
             ktx_data,
-            texture_start,
+            levels,
         }
     }
 
@@ -55,26 +155,578 @@ where
     ///
     /// # Panics
     ///
-    /// Input level is >= the `mipmap_levels` value.
+    /// Input level is >= the `mipmap_levels` value (or `1` when `mipmap_levels` is `0`),
+    /// or the level is missing from the data.
     #[inline]
     pub fn texture_level(&self, level: u32) -> &[u8] {
-        self.textures().nth(level as _).expect("invalid level")
+        assert!(level < self.mipmap_levels().max(1), "invalid level");
+        self.level_data(level as _).expect("invalid level")
+    }
+
+    /// Returns an iterator over the array elements of the input level, yielding
+    /// `(element_index, data)`. Each element contains all of its faces.
+    ///
+    /// Non-array textures yield a single element.
+    ///
+    /// Errors with [`KtxError::UnevenLevel`](../enum.KtxError.html#variant.UnevenLevel) if the
+    /// level data cannot be split into equally sized elements.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`Ktx::texture_level`](#method.texture_level).
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// for (element_index, data) in image.elements(0)? {
+    ///     // stream individual array layers
+    ///     # let _ = (element_index, data);
+    /// }
+    /// # Ok::<(), KtxError>(())
+    /// ```
+    pub fn elements(&self, level: u32) -> Result<Elements<'_>, KtxError> {
+        let data = self.texture_level(level);
+        let elements = self.array_elements().max(1);
+        let element_len = split_level_len(level, data.len() as _, elements as _)?;
+        Ok(Elements {
+            data,
+            element_len: element_len as _,
+            remaining: elements,
+            next_element: 0,
+        })
+    }
+
+    /// Returns the image data of a single depth slice of a face of an array element at the
+    /// input level. Each index starts at `0`.
+    ///
+    /// Non-array textures have a single layer, non-cubemap textures a single face &
+    /// non-3D textures a single depth slice. For 3D textures the number of depth slices
+    /// halves with each level.
+    ///
+    /// # Panics
+    ///
+    /// Any index is out of range, the level is missing from the data or the level length is
+    /// inconsistent with the header. See
+    /// [`Ktx::sub_image_location`](#method.sub_image_location) for a non-panicking alternative.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/uffizi_rgba16f_cube.ktx");
+    /// // level 1, -Y face
+    /// let face = image.sub_image(1, 0, 3, 0);
+    /// assert_eq!(face.len(), 256 * 256 * 8);
+    /// ```
+    #[inline]
+    pub fn sub_image(&self, level: u32, layer: u32, face: u32, depth_slice: u32) -> &[u8] {
+        let location = self
+            .sub_image_location(level, layer, face, depth_slice)
+            .expect("invalid sub-image");
+        &self.data()[location.range()]
+    }
+
+    /// Returns the location within the KTX [`data`](#method.data) of a single depth slice
+    /// of a face of an array element at the input level, see
+    /// [`Ktx::sub_image`](#method.sub_image).
+    ///
+    /// Returns `None` if any index is out of range, the level is missing from the data or
+    /// the level length is inconsistent with the header dimensions & format.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/uffizi_rgba16f_cube.ktx");
+    /// let location = image.sub_image_location(1, 0, 3, 0).unwrap();
+    /// assert_eq!(&image.data()[location.range()], image.sub_image(1, 0, 3, 0));
+    ///
+    /// assert_eq!(image.sub_image_location(1, 0, 6, 0), None);
+    /// ```
+    pub fn sub_image_location(
+        &self,
+        level: u32,
+        layer: u32,
+        face: u32,
+        depth_slice: u32,
+    ) -> Option<SubImageLocation> {
+        let layers = self.array_elements().max(1);
+        let faces = self.faces().max(1);
+        let (_, _, depth) = self.level_dimensions(level);
+        if level >= self.mipmap_levels().max(1)
+            || layer >= layers
+            || face >= faces
+            || depth_slice >= depth
+        {
+            return None;
+        }
+
+        // level data is ordered by array element, face, then depth slice
+        let range = self.level_range(level)?;
+        let images = layers as usize * faces as usize;
+        let face_image_size = has_face_image_size(&self.header);
+        let image_len = match image_rows(self, level) {
+            Some((rows, row_len)) => rows.checked_mul(padded_len(row_len))?,
+            None if face_image_size => {
+                read_u32(self.data(), range.start - 4, self.big_endian()) as usize
+            }
+            None => split_level_len(level, range.len() as u64, images as u64).ok()? as usize,
+        };
+        // each face followed by cubePadding
+        let stride = match face_image_size {
+            true => padded_len(image_len),
+            false => image_len,
+        };
+        if stride.checked_mul(images)? != range.len() || image_len % depth as usize != 0 {
+            return None;
+        }
+        let len = image_len / depth as usize;
+        let index = layer as usize * faces as usize + face as usize;
+        Some(SubImageLocation {
+            offset: range.start + index * stride + depth_slice as usize * len,
+            len,
+        })
     }
 
     /// Returns an iterator over the texture levels starting at level 0.
+    ///
+    /// Iteration panics if the data is missing texture levels, use
+    /// [`Ktx::try_textures`](#method.try_textures) to handle truncated data.
     #[inline]
     pub fn textures(&self) -> Textures<'_, D> {
         Textures {
             parent: self,
             next_level: 0,
-            level_end: self.texture_start as _,
+            end_level: self.mipmap_levels().max(1),
+        }
+    }
+
+    /// Returns an iterator over the texture levels starting at level 0, without panicking
+    /// on truncated data.
+    ///
+    /// Yields a [`KtxError`](../enum.KtxError.html) for the first level missing from the data,
+    /// after which iteration ends.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let data = include_bytes!("../tests/babg-bc3.ktx");
+    /// let image = Ktx::new(&data[..1000]);
+    ///
+    /// let mut textures = image.try_textures();
+    /// assert_eq!(textures.next(), Some(Err(KtxError::TruncatedLevel { level: 0 })));
+    /// assert_eq!(textures.next(), None);
+    /// ```
+    #[inline]
+    pub fn try_textures(&self) -> TryTextures<'_, D> {
+        TryTextures {
+            parent: self,
+            next_level: 0,
+            end_level: self.mipmap_levels().max(1),
+        }
+    }
+
+    /// Returns the complete KTX data, including the header.
+    #[inline]
+    pub fn data(&self) -> &[u8] {
+        self.ktx_data.as_ref()
+    }
+
+    /// Returns the total size in bytes of all texture level data present, including all faces &
+    /// array elements. Excludes `imageSize` values & padding.
+    #[inline]
+    pub fn total_texture_bytes(&self) -> usize {
+        (0..self.levels.present)
+            .filter_map(|level| self.level_data(level))
+            .map(<[u8]>::len)
+            .sum()
+    }
+
+    /// Returns an iterator over the key/value metadata pairs.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// for (key, value) in image.key_values() {
+    ///     println!("{key}: {value:?}");
+    /// }
+    /// ```
+    #[inline]
+    pub fn key_values(&self) -> KeyValues<'_> {
+        let data = self.data().get(64..self.levels.texture_start);
+        KeyValues::new(data.unwrap_or(&[]), self.header.big_endian)
+    }
+
+    /// Returns the value of the first key/value pair with the input key.
+    pub fn key_value(&self, key: &str) -> Option<&[u8]> {
+        self.key_values().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Returns the parsed `KTXorientation` metadata, if present & valid.
+    pub fn orientation(&self) -> Option<Orientation> {
+        Orientation::from_value(self.key_value(KTX_ORIENTATION)?)
+    }
+
+    /// Returns the parsed `KTXswizzle` metadata, if present & valid.
+    /// See [`parse_swizzle`](../key_value/fn.parse_swizzle.html).
+    pub fn swizzle(&self) -> Option<[Channel; 4]> {
+        key_value::parse_swizzle(self.key_value(KTX_SWIZZLE)?)
+    }
+}
+
+impl<D> Ktx<D>
+where
+    D: AsRef<[u8]>,
+{
+    /// Returns level data if present, using the precomputed level offsets.
+    #[inline]
+    fn level_data(&self, level: usize) -> Option<&[u8]> {
+        Some(&self.data()[self.level_range(level as _)?])
+    }
+}
+
+impl<D> Ktx<D>
+where
+    D: AsRef<[u8]> + AsMut<[u8]>,
+{
+    /// Returns mutable texture data at the input level, starting at `0`, allowing in-place
+    /// modification of owned data, e.g. `Ktx<Vec<u8>>`.
+    ///
+    /// Modifications are reflected in [`Ktx::data`](#method.data), level sizes are fixed.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`Ktx::texture_level`](#method.texture_level).
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let mut image = include_ktx!("../tests/babg-bc3.ktx").into_owned();
+    /// image.texture_level_mut(0).fill(0);
+    /// assert!(image.texture_level(0).iter().all(|b| *b == 0));
+    /// ```
+    #[inline]
+    pub fn texture_level_mut(&mut self, level: u32) -> &mut [u8] {
+        assert!(level < self.mipmap_levels().max(1), "invalid level");
+        let range = self.level_range(level).expect("invalid level");
+        &mut self.ktx_data.as_mut()[range]
+    }
+
+    /// Returns an iterator over mutable face images of the input level, yielding
+    /// `(face_index, data)`.
+    ///
+    /// Non-cubemap textures yield a single face. For array textures each array
+    /// element's faces are yielded in turn.
+    ///
+    /// Errors with [`KtxError::UnevenLevel`](../enum.KtxError.html#variant.UnevenLevel) if the
+    /// level data cannot be split into equally sized faces.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`Ktx::texture_level`](#method.texture_level).
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let mut image = include_ktx!("../tests/uffizi_rgba16f_cube.ktx").into_owned();
+    /// for (face_index, data) in image.faces_mut(0)? {
+    ///     // +X, -X, +Y, -Y, +Z, -Z faces
+    ///     # let _ = (face_index, data);
+    /// }
+    /// # Ok::<(), KtxError>(())
+    /// ```
+    pub fn faces_mut(&mut self, level: u32) -> Result<FacesMut<'_>, KtxError> {
+        let faces = self.faces().max(1);
+        let images = faces as u64 * self.array_elements().max(1) as u64;
+        let data = self.texture_level_mut(level);
+        let face_len = split_level_len(level, data.len() as _, images)?;
+        Ok(FacesMut {
+            data,
+            face_len: face_len as _,
+            remaining: images as _,
+            faces,
+            next_face: 0,
+        })
+    }
+}
+
+impl<D> Ktx<D> {
+    /// Consumes the `Ktx` returning the underlying KTX data.
+    #[inline]
+    pub fn into_inner(self) -> D {
+        self.ktx_data
+    }
+
+    /// Returns the range of the input level's data within the KTX [`data`](#method.data),
+    /// excluding `imageSize` & `mipPadding`. Returns `None` if the level is missing from
+    /// the data.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// assert_eq!(image.level_range(0), Some(68..52068));
+    /// assert_eq!(&image.data()[image.level_range(1).unwrap()], image.texture_level(1));
+    /// assert_eq!(image.level_range(8), None);
+    /// ```
+    #[inline]
+    pub fn level_range(&self, level: u32) -> Option<Range<usize>> {
+        let level = level as usize;
+        if level >= self.levels.present {
+            return None;
+        }
+        Some(self.levels.starts[level] + 4..self.levels.ends[level])
+    }
+}
+
+impl Ktx<&'static [u8]> {
+    /// Parses complete static KTX data, computing the header & level offsets.
+    ///
+    /// This is a `const fn` allowing parsing at compile time, as used by
+    /// [`include_ktx!`](../macro.include_ktx.html), so run-time texture lookup
+    /// requires no parsing.
+    ///
+    /// # Panics
+    ///
+    /// The data does not start with a valid KTX1 header, `mipmap_levels` exceeds 32 or
+    /// the data is missing texture levels.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// const IMAGE: Ktx<&[u8]> = Ktx::from_static(include_bytes!("../tests/babg-bc3.ktx"));
+    /// assert_eq!(IMAGE.pixel_width(), 260);
+    /// ```
+    pub const fn from_static(ktx_data: &'static [u8]) -> Self {
+        let header = assert_ktx_header(ktx_data);
+        assert!(
+            header.mipmap_levels as usize <= MAX_LEVELS,
+            "KTX mipmap_levels exceeds the maximum of 32"
+        );
+        let levels = LevelIndex::new(&header, ktx_data);
+
+        let expected_levels = match header.mipmap_levels {
+            0 => 1,
+            n => n as usize,
+        };
+        assert!(
+            levels.present == expected_levels,
+            "KTX data is missing texture levels"
+        );
+
+        Self {
+            header,
+            ktx_data,
+            levels,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl<D> Ktx<D>
+where
+    D: AsRef<[u8]>,
+{
+    /// Writes the complete KTX data, byte-exactly as parsed.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// let mut out = Vec::new();
+    /// image.write_to(&mut out)?;
+    /// assert_eq!(out, image.data());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    #[inline]
+    pub fn write_to(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
+        w.write_all(self.data())
+    }
+
+    /// Returns a copy of the complete KTX data, byte-exactly as parsed.
+    #[inline]
+    pub fn to_vec(&self) -> Vec<u8> {
+        self.data().to_vec()
+    }
+
+    /// Checks the key/value data is well formed & all texture levels are present.
+    pub(crate) fn check_complete(&self) -> Result<(), KtxError> {
+        let key_values = self.data().get(64..self.levels.texture_start);
+        match key_values {
+            Some(data) if KeyValues::new(data, self.header.big_endian).is_well_formed() => {}
+            _ => return Err(KtxError::InvalidKeyValues),
+        }
+        self.try_textures().try_for_each(|level| level.map(drop))
+    }
+
+    /// Writes the KTX data normalized to little endian.
+    ///
+    /// The header, key/value sizes & `imageSize` values are written little endian &
+    /// texture data is endian converted when `gl_type_size` is `2` or `4`.
+    ///
+    /// Errors with `InvalidData`, wrapping a [`KtxError`](../enum.KtxError.html), if the
+    /// key/value data is malformed or texture levels are missing, before writing anything.
+    pub fn write_little_endian_to(&self, mut w: impl std::io::Write) -> std::io::Result<()> {
+        use crate::format::{image_size_field, padded_len};
+
+        let invalid_data = |err| std::io::Error::new(std::io::ErrorKind::InvalidData, err);
+        self.check_complete().map_err(invalid_data)?;
+
+        let mut key_value_data = Vec::new();
+        for (key, value) in self.key_values() {
+            let pair_len = (key.len() + 1 + value.len()) as u32;
+            key_value_data.extend_from_slice(&pair_len.to_le_bytes());
+            key_value_data.extend_from_slice(key.as_bytes());
+            key_value_data.push(0);
+            key_value_data.extend_from_slice(value);
+            key_value_data.resize(padded_len(key_value_data.len()), 0);
+        }
+
+        let mut header = self.header;
+        header.big_endian = false;
+        header.bytes_of_key_value_data = key_value_data.len() as u32;
+        w.write_all(&header.to_bytes())?;
+        w.write_all(&key_value_data)?;
+
+        let swap = self.header.big_endian && matches!(self.header.gl_type_size, 2 | 4);
+        for data in self.textures() {
+            // level data was read with a u32 imageSize
+            let image_size = image_size_field(data.len(), &self.header).unwrap_or(u32::MAX);
+            w.write_all(&image_size.to_le_bytes())?;
+            if swap {
+                let mut data = data.to_vec();
+                for value in data.chunks_exact_mut(self.header.gl_type_size as usize) {
+                    value.reverse();
+                }
+                w.write_all(&data)?;
+            } else {
+                w.write_all(data)?;
+            }
+            w.write_all(&[0; 3][..padded_len(data.len()) - data.len()])?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(feature = "hash")]
+impl<D> Ktx<D>
+where
+    D: AsRef<[u8]>,
+{
+    /// Returns the blake3 hash of all texture level data present, as stored, in level order.
+    /// Excludes `imageSize` values & padding.
+    ///
+    /// Requires the `hash` feature.
+    pub fn content_hash(&self) -> blake3::Hash {
+        let mut hasher = blake3::Hasher::new();
+        for level in (0..self.levels.present).filter_map(|level| self.level_data(level)) {
+            hasher.update(level);
+        }
+        hasher.finalize()
+    }
+
+    /// Returns whether the texture level data matches the `KTXhash` metadata, written by
+    /// [`KtxBuilder::content_hash`](../write/struct.KtxBuilder.html#method.content_hash).
+    ///
+    /// Returns `None` if the data has no valid `KTXhash` metadata. Truncated data does not match.
+    ///
+    /// Requires the `hash` feature.
+    pub fn verify_hash(&self) -> Option<bool> {
+        let expected = key_value::parse_hash(self.key_value(key_value::KTX_HASH)?)?;
+        // blake3::Hash equality is constant-time
+        Some(self.content_hash() == expected)
+    }
+}
+
+#[cfg(feature = "rayon")]
+impl<D> Ktx<D>
+where
+    D: AsRef<[u8]> + Sync,
+{
+    /// Returns a parallel iterator over the texture levels starting at level 0.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`Ktx::textures`](#method.textures), the data is missing texture levels.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// use rayon::prelude::*;
+    ///
+    /// let image = include_ktx!("../tests/babg-bc3.ktx");
+    /// // transcode each level using all cores
+    /// let levels: Vec<Vec<u8>> = image.par_textures().map(|data| data.to_vec()).collect();
+    /// assert_eq!(levels.len(), image.textures().len());
+    /// ```
+    #[inline]
+    pub fn par_textures(&self) -> impl rayon::iter::IndexedParallelIterator<Item = &[u8]> {
+        use rayon::prelude::*;
+
+        (0..self.mipmap_levels().max(1))
+            .into_par_iter()
+            .map(move |level| self.texture_level(level))
+    }
+
+    /// Returns a parallel iterator over the texture levels starting at level 0,
+    /// converted to native endianness.
+    ///
+    /// Texture data is endian converted when `gl_type_size` is `2` or `4` & the data
+    /// endianness differs from the native endianness, otherwise levels are borrowed.
+    /// Large levels are also converted in parallel.
+    ///
+    /// Requires the `rayon` feature.
+    ///
+    /// # Panics
+    ///
+    /// Same as [`Ktx::textures`](#method.textures), the data is missing texture levels.
+    pub fn par_native_endian_textures(
+        &self,
+    ) -> impl rayon::iter::IndexedParallelIterator<Item = std::borrow::Cow<'_, [u8]>> {
+        use rayon::prelude::*;
+        use std::borrow::Cow;
+
+        let type_size = self.header.gl_type_size as usize;
+        let swap =
+            self.header.big_endian != cfg!(target_endian = "big") && matches!(type_size, 2 | 4);
+        self.par_textures().map(move |data| match swap {
+            false => Cow::Borrowed(data),
+            true => {
+                let mut data = data.to_vec();
+                data.par_chunks_exact_mut(type_size)
+                    .with_min_len(4096)
+                    .for_each(<[u8]>::reverse);
+                Cow::Owned(data)
+            }
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl Ktx<&[u8]> {
+    /// Copies the borrowed KTX data into a new `Ktx` that owns its data.
+    ///
+    /// # Example
+    /// ```
+    /// # use ktx::*;
+    /// let image: Ktx<Vec<u8>> = include_ktx!("../tests/babg-bc3.ktx").into_owned();
+    /// assert_eq!(image.pixel_width(), 260);
+    /// ```
+    #[inline]
+    pub fn into_owned(self) -> Ktx<Vec<u8>> {
+        Ktx {
+            header: self.header,
+            ktx_data: self.ktx_data.to_vec(),
+            levels: self.levels,
         }
     }
 }
 
 impl<D> From<D> for Ktx<D>
 where
-    D: Deref<Target = [u8]>,
+    D: AsRef<[u8]>,
 {
     #[inline]
     fn from(d: D) -> Self {
@@ -82,6 +734,24 @@ where
     }
 }
 
+/// Location of sub-image data within KTX data, see
+/// [`Ktx::sub_image_location`](struct.Ktx.html#method.sub_image_location).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubImageLocation {
+    /// Byte offset from the start of the KTX data.
+    pub offset: usize,
+    /// Length in bytes.
+    pub len: usize,
+}
+
+impl SubImageLocation {
+    /// Returns the `offset..offset + len` range.
+    #[inline]
+    pub fn range(&self) -> Range<usize> {
+        self.offset..self.offset + self.len
+    }
+}
+
 /// Iterator over texture level data.
 ///
 /// For cubemap textures each level will contain all 6 faces
@@ -90,51 +760,206 @@ where
 pub struct Textures<'a, D> {
     parent: &'a Ktx<D>,
     next_level: u32,
-    level_end: usize,
+    end_level: u32,
 }
 
 impl<'a, D> Iterator for Textures<'a, D>
 where
-    D: Deref<Target = [u8]>,
+    D: AsRef<[u8]>,
 {
     type Item = &'a [u8];
 
     fn next(&mut self) -> Option<Self::Item> {
-        if self.next_level >= self.parent.mipmap_levels() {
+        if self.next_level >= self.end_level {
             None
         } else {
+            let level = self.next_level;
             self.next_level += 1;
+            Some(self.parent.texture_level(level))
+        }
+    }
 
-            let l_end = self.level_end;
-            let mut next_lvl_len = if self.parent.big_endian() {
-                BigEndian::read_u32(&self.parent.ktx_data[l_end..l_end + 4])
-            } else {
-                LittleEndian::read_u32(&self.parent.ktx_data[l_end..l_end + 4])
-            };
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.len();
+        (len, Some(len))
+    }
 
-            if self.parent.array_elements() == 0 && self.parent.faces() == 6 {
-                // Multiply for each face, see https://www.khronos.org/registry/KTX/specs/1.0/ktxspec_v1.html#2.16
-                next_lvl_len *= 6;
-            }
+    #[inline]
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.next_level = self.next_level.saturating_add(n as _).min(self.end_level);
+        self.next()
+    }
+}
 
-            self.level_end = l_end + 4 + next_lvl_len as usize;
-            Some(&self.parent.ktx_data[l_end + 4..self.level_end])
+impl<D> DoubleEndedIterator for Textures<'_, D>
+where
+    D: AsRef<[u8]>,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next_level >= self.end_level {
+            None
+        } else {
+            self.end_level -= 1;
+            Some(self.parent.texture_level(self.end_level))
         }
     }
 }
 
-impl<D> core::iter::FusedIterator for Textures<'_, D> where D: Deref<Target = [u8]> {}
+impl<D> ExactSizeIterator for Textures<'_, D>
+where
+    D: AsRef<[u8]>,
+{
+    #[inline]
+    fn len(&self) -> usize {
+        (self.end_level - self.next_level) as _
+    }
+}
+
+impl<D> core::iter::FusedIterator for Textures<'_, D> where D: AsRef<[u8]> {}
+
+/// Iterator over the array elements of a texture level, yielding `(element_index, data)`.
+/// See [`Ktx::elements`](struct.Ktx.html#method.elements).
+#[derive(Debug, Clone)]
+pub struct Elements<'a> {
+    data: &'a [u8],
+    element_len: usize,
+    remaining: u32,
+    next_element: u32,
+}
+
+impl<'a> Iterator for Elements<'a> {
+    type Item = (u32, &'a [u8]);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (element, rest) = self.data.split_at(self.element_len);
+        self.data = rest;
+        self.remaining -= 1;
+        let index = self.next_element;
+        self.next_element += 1;
+        Some((index, element))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining as _, Some(self.remaining as _))
+    }
+}
+
+impl ExactSizeIterator for Elements<'_> {}
+
+impl core::iter::FusedIterator for Elements<'_> {}
+
+/// Iterator over mutable face images of a texture level, yielding `(face_index, data)`.
+/// See [`Ktx::faces_mut`](struct.Ktx.html#method.faces_mut).
+#[derive(Debug)]
+pub struct FacesMut<'a> {
+    data: &'a mut [u8],
+    face_len: usize,
+    remaining: usize,
+    faces: u32,
+    next_face: u32,
+}
+
+impl<'a> Iterator for FacesMut<'a> {
+    type Item = (u32, &'a mut [u8]);
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining == 0 {
+            return None;
+        }
+        let (face, rest) = core::mem::take(&mut self.data).split_at_mut(self.face_len);
+        self.data = rest;
+        self.remaining -= 1;
+        let index = self.next_face;
+        self.next_face = (self.next_face + 1) % self.faces;
+        Some((index, face))
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (self.remaining, Some(self.remaining))
+    }
+}
+
+impl ExactSizeIterator for FacesMut<'_> {}
+
+impl core::iter::FusedIterator for FacesMut<'_> {}
+
+/// Iterator over texture level data, yielding an error for truncated data.
+/// See [`Ktx::try_textures`](struct.Ktx.html#method.try_textures).
+#[derive(Debug)]
+pub struct TryTextures<'a, D> {
+    parent: &'a Ktx<D>,
+    next_level: u32,
+    end_level: u32,
+}
+
+impl<'a, D> Iterator for TryTextures<'a, D>
+where
+    D: AsRef<[u8]>,
+{
+    type Item = Result<&'a [u8], KtxError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next_level >= self.end_level {
+            None
+        } else {
+            let level = self.next_level;
+            self.next_level += 1;
+            match self.parent.level_data(level as _) {
+                Some(data) => Some(Ok(data)),
+                None => {
+                    self.next_level = self.end_level;
+                    Some(Err(match level as usize >= MAX_LEVELS {
+                        true => KtxError::TooManyLevels {
+                            levels: self.parent.mipmap_levels(),
+                        },
+                        false => KtxError::TruncatedLevel { level },
+                    }))
+                }
+            }
+        }
+    }
+}
+
+impl<D> core::iter::FusedIterator for TryTextures<'_, D> where D: AsRef<[u8]> {}
 
 /// Wrapper for `include_bytes!` returning `Ktx<'static [u8]>`
 ///
+/// The KTX data is parsed at compile time using
+/// [`Ktx::from_static`](slice/struct.Ktx.html#method.from_static), so including a file
+/// that is not complete KTX1 data fails to compile.
+///
 /// # Example
 /// ```
 /// use ktx::{include_ktx, Ktx};
 /// let image: Ktx<&'static [u8]> = include_ktx!("../tests/babg-bc3.ktx");
 /// ```
+///
+/// ```compile_fail
+/// // Cargo.toml is not KTX data
+/// let image = ktx::include_ktx!("../Cargo.toml");
+/// ```
 #[macro_export]
 macro_rules! include_ktx {
-    ($path:tt) => {
-        $crate::Ktx::new(include_bytes!($path) as &[u8])
-    };
+    ($path:tt) => {{
+        const KTX: $crate::Ktx<&[u8]> = $crate::Ktx::from_static(include_bytes!($path));
+        KTX
+    }};
+}
+
+/// Returns the parsed header, panicking if the data does not start with a valid KTX1 header.
+const fn assert_ktx_header(ktx_data: &[u8]) -> KtxHeader {
+    match KtxHeader::try_from_prefix(ktx_data) {
+        Ok(header) => header,
+        Err(HeaderError::TooShort) => panic!("KTX data is too short for a header"),
+        Err(HeaderError::InvalidIdentifier) => panic!("Not KTX1"),
+        Err(HeaderError::InvalidEndianness) => panic!("Invalid KTX endianness"),
+    }
 }
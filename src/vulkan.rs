@@ -0,0 +1,278 @@
+//! Vulkan image creation helpers using [ash](https://docs.rs/ash).
+//!
+//! Requires the `ash` feature.
+use crate::{
+    format::{self, *},
+    header::{KtxInfo, TextureKind},
+};
+use ash::vk;
+use std::{error::Error, fmt};
+
+/// Vulkan image creation & upload info for KTX data.
+/// See [`image_descriptor`](fn.image_descriptor.html).
+#[derive(Debug, Clone)]
+pub struct ImageDescriptor {
+    /// Image create info using the KTX format, extent, mip levels & array layers.
+    ///
+    /// Usage is `TRANSFER_DST | SAMPLED` with optimal tiling, these may be changed as required.
+    pub create_info: vk::ImageCreateInfo<'static>,
+    /// Buffer to image copy regions, one for each texture level in level order.
+    ///
+    /// Each region copies all array layers & faces of the level from
+    /// `buffer_offset` in a staging buffer.
+    pub regions: Vec<vk::BufferImageCopy>,
+    /// Staging buffer size required to contain all texture levels at the region offsets.
+    pub buffer_size: vk::DeviceSize,
+}
+
+/// Error describing KTX data for Vulkan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VulkanError {
+    /// The `glInternalFormat` has no known Vulkan equivalent.
+    UnsupportedFormat(u32),
+    /// Uncompressed row padding is not a whole number of pixels, so cannot be described
+    /// with `buffer_row_length`.
+    ///
+    /// Rows are padded to 4 bytes, so this affects 3 & 6 byte pixel formats, e.g. `RGB8` &
+    /// `RGB16F`, at levels with a width not a multiple of 4, such as the 1 & 2 pixel wide
+    /// levels of every full mipmap chain. Such textures may be converted to a 4 channel
+    /// format, or have their rows repacked tightly & described with custom regions.
+    RowPadding,
+    /// `mipmap_levels` exceeds the levels of a full mipmap chain for the texture dimensions,
+    /// see [`KtxInfo::level_count_for_full_chain`](../header/trait.KtxInfo.html#method.level_count_for_full_chain).
+    TooManyLevels { levels: u32, max: u32 },
+    /// The header dimensions, `faces` & `array_elements` give a size too large to address.
+    DimensionsOverflow,
+}
+
+impl fmt::Display for VulkanError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnsupportedFormat(format) => {
+                write!(f, "no Vulkan format for glInternalFormat {format:#x}")
+            }
+            Self::RowPadding => f.write_str("row padding is not a whole number of pixels"),
+            Self::TooManyLevels { levels, max } => write!(
+                f,
+                "{levels} mipmap levels exceeds the full chain of {max} levels"
+            ),
+            Self::DimensionsOverflow => f.write_str("header dimensions overflow"),
+        }
+    }
+}
+
+impl Error for VulkanError {}
+
+/// Returns the Vulkan format equivalent to the `glInternalFormat`, if known.
+pub fn vk_format(info: &impl KtxInfo) -> Option<vk::Format> {
+    use vk::Format as F;
+
+    Some(match info.gl_internal_format() {
+        R8 => F::R8_UNORM,
+        RG8 => F::R8G8_UNORM,
+        RGB8 => F::R8G8B8_UNORM,
+        RGBA8 => F::R8G8B8A8_UNORM,
+        SRGB8 => F::R8G8B8_SRGB,
+        SRGB8_ALPHA8 => F::R8G8B8A8_SRGB,
+        R16F => F::R16_SFLOAT,
+        RG16F => F::R16G16_SFLOAT,
+        RGB16F => F::R16G16B16_SFLOAT,
+        RGBA16F => F::R16G16B16A16_SFLOAT,
+        R32F => F::R32_SFLOAT,
+        RG32F => F::R32G32_SFLOAT,
+        RGB32F => F::R32G32B32_SFLOAT,
+        RGBA32F => F::R32G32B32A32_SFLOAT,
+        COMPRESSED_RGB_S3TC_DXT1_EXT => F::BC1_RGB_UNORM_BLOCK,
+        COMPRESSED_SRGB_S3TC_DXT1_EXT => F::BC1_RGB_SRGB_BLOCK,
+        COMPRESSED_RGBA_S3TC_DXT1_EXT => F::BC1_RGBA_UNORM_BLOCK,
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT1_EXT => F::BC1_RGBA_SRGB_BLOCK,
+        COMPRESSED_RGBA_S3TC_DXT3_EXT => F::BC2_UNORM_BLOCK,
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT3_EXT => F::BC2_SRGB_BLOCK,
+        COMPRESSED_RGBA_S3TC_DXT5_EXT => F::BC3_UNORM_BLOCK,
+        COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT => F::BC3_SRGB_BLOCK,
+        COMPRESSED_RED_RGTC1 => F::BC4_UNORM_BLOCK,
+        COMPRESSED_SIGNED_RED_RGTC1 => F::BC4_SNORM_BLOCK,
+        COMPRESSED_RG_RGTC2 => F::BC5_UNORM_BLOCK,
+        COMPRESSED_SIGNED_RG_RGTC2 => F::BC5_SNORM_BLOCK,
+        COMPRESSED_RGB_BPTC_UNSIGNED_FLOAT => F::BC6H_UFLOAT_BLOCK,
+        COMPRESSED_RGB_BPTC_SIGNED_FLOAT => F::BC6H_SFLOAT_BLOCK,
+        COMPRESSED_RGBA_BPTC_UNORM => F::BC7_UNORM_BLOCK,
+        COMPRESSED_SRGB_ALPHA_BPTC_UNORM => F::BC7_SRGB_BLOCK,
+        // ETC2 is backwards compatible with ETC1
+        ETC1_RGB8_OES | COMPRESSED_RGB8_ETC2 => F::ETC2_R8G8B8_UNORM_BLOCK,
+        COMPRESSED_SRGB8_ETC2 => F::ETC2_R8G8B8_SRGB_BLOCK,
+        COMPRESSED_RGB8_PUNCHTHROUGH_ALPHA1_ETC2 => F::ETC2_R8G8B8A1_UNORM_BLOCK,
+        COMPRESSED_SRGB8_PUNCHTHROUGH_ALPHA1_ETC2 => F::ETC2_R8G8B8A1_SRGB_BLOCK,
+        COMPRESSED_RGBA8_ETC2_EAC => F::ETC2_R8G8B8A8_UNORM_BLOCK,
+        COMPRESSED_SRGB8_ALPHA8_ETC2_EAC => F::ETC2_R8G8B8A8_SRGB_BLOCK,
+        COMPRESSED_R11_EAC => F::EAC_R11_UNORM_BLOCK,
+        COMPRESSED_SIGNED_R11_EAC => F::EAC_R11_SNORM_BLOCK,
+        COMPRESSED_RG11_EAC => F::EAC_R11G11_UNORM_BLOCK,
+        COMPRESSED_SIGNED_RG11_EAC => F::EAC_R11G11_SNORM_BLOCK,
+        COMPRESSED_RGB_PVRTC_2BPPV1_IMG | COMPRESSED_RGBA_PVRTC_2BPPV1_IMG => {
+            F::PVRTC1_2BPP_UNORM_BLOCK_IMG
+        }
+        COMPRESSED_RGB_PVRTC_4BPPV1_IMG | COMPRESSED_RGBA_PVRTC_4BPPV1_IMG => {
+            F::PVRTC1_4BPP_UNORM_BLOCK_IMG
+        }
+        COMPRESSED_SRGB_PVRTC_2BPPV1_EXT | COMPRESSED_SRGB_ALPHA_PVRTC_2BPPV1_EXT => {
+            F::PVRTC1_2BPP_SRGB_BLOCK_IMG
+        }
+        COMPRESSED_SRGB_PVRTC_4BPPV1_EXT | COMPRESSED_SRGB_ALPHA_PVRTC_4BPPV1_EXT => {
+            F::PVRTC1_4BPP_SRGB_BLOCK_IMG
+        }
+        COMPRESSED_RGBA_PVRTC_2BPPV2_IMG => F::PVRTC2_2BPP_UNORM_BLOCK_IMG,
+        COMPRESSED_RGBA_PVRTC_4BPPV2_IMG => F::PVRTC2_4BPP_UNORM_BLOCK_IMG,
+        // Vulkan ASTC formats alternate unorm, srgb in the same block order as GL
+        f @ COMPRESSED_RGBA_ASTC_4X4_KHR..=COMPRESSED_RGBA_ASTC_12X12_KHR => {
+            let index = (f - COMPRESSED_RGBA_ASTC_4X4_KHR) as i32;
+            F::from_raw(F::ASTC_4X4_UNORM_BLOCK.as_raw() + index * 2)
+        }
+        f @ COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR..=COMPRESSED_SRGB8_ALPHA8_ASTC_12X12_KHR => {
+            let index = (f - COMPRESSED_SRGB8_ALPHA8_ASTC_4X4_KHR) as i32;
+            F::from_raw(F::ASTC_4X4_SRGB_BLOCK.as_raw() + index * 2)
+        }
+        _ => return None,
+    })
+}
+
+/// Returns the Vulkan image create info & per-level staging buffer copy regions
+/// for the KTX data.
+///
+/// * Cubemaps & cubemap arrays are created `CUBE_COMPATIBLE` with 6 array layers per element.
+/// * When `mipmap_levels` is `0` the image is created with a single level, mipmaps
+///   may be generated with blits after upload.
+/// * Each level's region `buffer_offset` is aligned to 4 bytes & the texel block size.
+///   Level data should be written to the staging buffer at these offsets, converted to
+///   native endianness if required.
+/// * Errors with [`RowPadding`](enum.VulkanError.html#variant.RowPadding) for 3 & 6 byte
+///   pixel formats, e.g. `RGB8`, with levels whose 4 byte row padding is not a whole number
+///   of pixels, which includes any full mipmap chain.
+///
+/// # Example
+/// ```
+/// use ktx::{vulkan, Ktx};
+///
+/// # let data: &[u8] = include_bytes!("../tests/babg-bc3.ktx");
+/// let ktx = Ktx::new(data);
+/// let descriptor = vulkan::image_descriptor(&ktx)?;
+/// assert_eq!(descriptor.create_info.format, ash::vk::Format::BC3_UNORM_BLOCK);
+///
+/// let mut staging = vec![0; descriptor.buffer_size as usize];
+/// for (region, level) in descriptor.regions.iter().zip(ktx.textures()) {
+///     let offset = region.buffer_offset as usize;
+///     staging[offset..offset + level.len()].copy_from_slice(level);
+/// }
+/// // upload `staging` & record `vkCmdCopyBufferToImage` with `descriptor.regions`
+/// # Ok::<(), vulkan::VulkanError>(())
+/// ```
+pub fn image_descriptor(info: &impl KtxInfo) -> Result<ImageDescriptor, VulkanError> {
+    let format =
+        vk_format(info).ok_or(VulkanError::UnsupportedFormat(info.gl_internal_format()))?;
+
+    let (image_type, flags) = match info.kind() {
+        TextureKind::D1 | TextureKind::D1Array => (vk::ImageType::TYPE_1D, Default::default()),
+        TextureKind::D3 => (vk::ImageType::TYPE_3D, Default::default()),
+        TextureKind::Cube | TextureKind::CubeArray => (
+            vk::ImageType::TYPE_2D,
+            vk::ImageCreateFlags::CUBE_COMPATIBLE,
+        ),
+        TextureKind::D2 | TextureKind::D2Array => (vk::ImageType::TYPE_2D, Default::default()),
+    };
+    let mip_levels = info.mipmap_levels().max(1);
+    let max_levels = info.level_count_for_full_chain();
+    if mip_levels > max_levels {
+        return Err(VulkanError::TooManyLevels {
+            levels: mip_levels,
+            max: max_levels,
+        });
+    }
+    let array_layers = info
+        .array_elements()
+        .max(1)
+        .checked_mul(info.faces().max(1))
+        .ok_or(VulkanError::DimensionsOverflow)?;
+
+    let create_info = vk::ImageCreateInfo::default()
+        .flags(flags)
+        .image_type(image_type)
+        .format(format)
+        .extent(level_extent(info, 0))
+        .mip_levels(mip_levels)
+        .array_layers(array_layers)
+        .samples(vk::SampleCountFlags::TYPE_1)
+        .tiling(vk::ImageTiling::OPTIMAL)
+        .usage(vk::ImageUsageFlags::TRANSFER_DST | vk::ImageUsageFlags::SAMPLED)
+        .sharing_mode(vk::SharingMode::EXCLUSIVE)
+        .initial_layout(vk::ImageLayout::UNDEFINED);
+
+    let unsupported = || VulkanError::UnsupportedFormat(info.gl_internal_format());
+    let texel_block_size = match info.is_compressed() {
+        true => info.internal_format().block_size(),
+        false => format::pixel_size(info.gl_type(), info.gl_format()),
+    }
+    .ok_or_else(unsupported)? as u64;
+    let alignment = lcm(4, texel_block_size);
+
+    let mut regions = Vec::with_capacity(mip_levels as _);
+    let mut buffer_size: u64 = 0;
+    for level in 0..mip_levels {
+        let (_, row_len) = format::image_rows(info, level).ok_or_else(unsupported)?;
+        let padded_row_len = format::padded_len(row_len) as u64;
+        let buffer_row_length = match info.is_compressed() || padded_row_len == row_len as u64 {
+            true => 0,
+            false if padded_row_len.is_multiple_of(texel_block_size) => {
+                (padded_row_len / texel_block_size) as u32
+            }
+            false => return Err(VulkanError::RowPadding),
+        };
+
+        let buffer_offset = buffer_size
+            .div_ceil(alignment)
+            .checked_mul(alignment)
+            .ok_or(VulkanError::DimensionsOverflow)?;
+        let level_len = format::expected_level_len(info, level)
+            .map_err(|_| VulkanError::DimensionsOverflow)?
+            .ok_or_else(unsupported)?;
+        buffer_size = buffer_offset
+            .checked_add(level_len as u64)
+            .ok_or(VulkanError::DimensionsOverflow)?;
+
+        regions.push(
+            vk::BufferImageCopy::default()
+                .buffer_offset(buffer_offset)
+                .buffer_row_length(buffer_row_length)
+                .image_subresource(
+                    vk::ImageSubresourceLayers::default()
+                        .aspect_mask(vk::ImageAspectFlags::COLOR)
+                        .mip_level(level)
+                        .layer_count(array_layers),
+                )
+                .image_extent(level_extent(info, level)),
+        );
+    }
+
+    Ok(ImageDescriptor {
+        create_info,
+        regions,
+        buffer_size,
+    })
+}
+
+/// Returns the texel extent of the input level.
+fn level_extent(info: &impl KtxInfo, level: u32) -> vk::Extent3D {
+    let (width, height, depth) = info.level_dimensions(level);
+    vk::Extent3D {
+        width,
+        height,
+        depth,
+    }
+}
+
+fn lcm(a: u64, b: u64) -> u64 {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        (x, y) = (y, x % y);
+    }
+    a / x * b
+}
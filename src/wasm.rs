@@ -0,0 +1,133 @@
+//! Browser bindings using [wasm-bindgen](https://docs.rs/wasm-bindgen).
+//!
+//! Requires the `wasm` feature.
+//!
+//! # Example
+//! ```js
+//! const data = new Uint8Array(await (await fetch("logo.ktx")).arrayBuffer());
+//! const header = parseHeader(data);
+//! console.log(header.pixelWidth, header.pixelHeight);
+//!
+//! const texture = new KtxTexture(data);
+//! for (let level = 0; level < texture.levelCount; level++) {
+//!     gl.compressedTexImage2D(/* ... */, texture.level(level));
+//! }
+//! ```
+use crate::{header::KtxHeader, slice::Ktx, KtxInfo};
+use js_sys::Uint8Array;
+use wasm_bindgen::prelude::*;
+
+/// Parses & validates the KTX header from the first 64 bytes of the data.
+///
+/// Only the header bytes are copied out of the JS array, so this is cheap for large textures.
+#[wasm_bindgen(js_name = parseHeader)]
+pub fn parse_header(data: &Uint8Array) -> Result<Header, JsError> {
+    let mut prefix = [0; 64];
+    let len = data.length().min(64);
+    data.subarray(0, len).copy_to(&mut prefix[..len as usize]);
+    let header = KtxHeader::try_from_prefix(&prefix[..len as usize])?;
+    Ok(Header(header))
+}
+
+/// KTX header values, see [`KtxInfo`](../header/trait.KtxInfo.html).
+#[wasm_bindgen(js_name = KtxHeader)]
+#[derive(Debug, Clone, Copy)]
+pub struct Header(KtxHeader);
+
+#[wasm_bindgen(js_class = KtxHeader)]
+impl Header {
+    #[wasm_bindgen(getter, js_name = glType)]
+    pub fn gl_type(&self) -> u32 {
+        self.0.gl_type()
+    }
+
+    #[wasm_bindgen(getter, js_name = glTypeSize)]
+    pub fn gl_type_size(&self) -> u32 {
+        self.0.gl_type_size()
+    }
+
+    #[wasm_bindgen(getter, js_name = glFormat)]
+    pub fn gl_format(&self) -> u32 {
+        self.0.gl_format()
+    }
+
+    #[wasm_bindgen(getter, js_name = glInternalFormat)]
+    pub fn gl_internal_format(&self) -> u32 {
+        self.0.gl_internal_format()
+    }
+
+    #[wasm_bindgen(getter, js_name = glBaseInternalFormat)]
+    pub fn gl_base_internal_format(&self) -> u32 {
+        self.0.gl_base_internal_format()
+    }
+
+    #[wasm_bindgen(getter, js_name = pixelWidth)]
+    pub fn pixel_width(&self) -> u32 {
+        self.0.pixel_width()
+    }
+
+    #[wasm_bindgen(getter, js_name = pixelHeight)]
+    pub fn pixel_height(&self) -> u32 {
+        self.0.pixel_height()
+    }
+
+    #[wasm_bindgen(getter, js_name = pixelDepth)]
+    pub fn pixel_depth(&self) -> u32 {
+        self.0.pixel_depth()
+    }
+
+    #[wasm_bindgen(getter, js_name = arrayElements)]
+    pub fn array_elements(&self) -> u32 {
+        self.0.array_elements()
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn faces(&self) -> u32 {
+        self.0.faces()
+    }
+
+    #[wasm_bindgen(getter, js_name = mipmapLevels)]
+    pub fn mipmap_levels(&self) -> u32 {
+        self.0.mipmap_levels()
+    }
+
+    #[wasm_bindgen(getter, js_name = isCompressed)]
+    pub fn is_compressed(&self) -> bool {
+        self.0.is_compressed()
+    }
+}
+
+/// Complete KTX data copied into wasm memory, providing views of each texture level.
+#[wasm_bindgen(js_name = KtxTexture)]
+pub struct Texture(Ktx<Vec<u8>>);
+
+#[wasm_bindgen(js_class = KtxTexture)]
+impl Texture {
+    /// Copies & parses complete KTX data.
+    #[wasm_bindgen(constructor)]
+    pub fn new(data: &Uint8Array) -> Result<Texture, JsError> {
+        Ok(Self(Ktx::try_new(data.to_vec())?))
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn header(&self) -> Header {
+        Header(*self.0.as_ref())
+    }
+
+    /// Number of stored texture levels.
+    #[wasm_bindgen(getter, js_name = levelCount)]
+    pub fn level_count(&self) -> u32 {
+        self.0.mipmap_levels().max(1)
+    }
+
+    /// Returns a view of the texture level data in wasm memory, without copying.
+    ///
+    /// The view is invalidated if wasm memory grows, so should be used, e.g. uploaded to
+    /// WebGL, before further calls into wasm.
+    pub fn level(&self, level: u32) -> Result<Uint8Array, JsError> {
+        let data = self.0.try_textures().nth(level as _);
+        let data = data.ok_or_else(|| JsError::new("invalid level"))??;
+        // Safety: view is documented to be used before wasm memory may grow
+        Ok(unsafe { Uint8Array::view(data) })
+    }
+}
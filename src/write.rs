@@ -0,0 +1,1015 @@
+use crate::{
+    format::{self, expected_level_len, padded_len},
+    header::*,
+    key_value::{Channel, Orientation, KTX_ORIENTATION, KTX_SWIZZLE},
+};
+use std::{error::Error, fmt, io};
+
+/// KTX texture storage format writer. Builds complete KTX data from header values & texture
+/// level data.
+///
+/// Writes little endian KTX data. Provides [`KtxInfo`](../header/trait.KtxInfo.html) for the
+/// header values set so far.
+///
+/// # Example
+/// ```
+/// use ktx::{format::*, KtxBuilder, KtxInfo};
+///
+/// let ktx_data = KtxBuilder::new()
+///     .gl_type(UNSIGNED_BYTE)
+///     .gl_format(RGBA)
+///     .gl_internal_format(RGBA8)
+///     .gl_base_internal_format(RGBA)
+///     .pixel_width(2)
+///     .pixel_height(2)
+///     .level(vec![255; 2 * 2 * 4])
+///     .to_vec()?;
+///
+/// let image = ktx::Ktx::new(ktx_data.as_slice());
+/// assert_eq!(image.pixel_width(), 2);
+/// # Ok::<(), ktx::write::BuildError>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct KtxBuilder {
+    pub(crate) header: KtxHeader,
+    pub(crate) key_value_data: Vec<u8>,
+    pub(crate) levels: Vec<Vec<u8>>,
+}
+
+impl AsRef<KtxHeader> for KtxBuilder {
+    #[inline]
+    fn as_ref(&self) -> &KtxHeader {
+        &self.header
+    }
+}
+
+impl Default for KtxBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl KtxBuilder {
+    /// Returns a new builder for a non-array, non-cubemap texture with `1` mipmap level &
+    /// `gl_type_size` `1`. All other header values are `0`.
+    pub fn new() -> Self {
+        Self {
+            header: KtxHeader {
+                big_endian: false,
+                gl_type: 0,
+                gl_type_size: 1,
+                gl_format: 0,
+                gl_internal_format: 0,
+                gl_base_internal_format: 0,
+                pixel_width: 0,
+                pixel_height: 0,
+                pixel_depth: 0,
+                array_elements: 0,
+                faces: 1,
+                mipmap_levels: 1,
+                bytes_of_key_value_data: 0,
+            },
+            key_value_data: Vec::new(),
+            levels: Vec::new(),
+        }
+    }
+
+    /// Sets `glType`, see [`KtxInfo::gl_type`](../header/trait.KtxInfo.html#tymethod.gl_type).
+    pub fn gl_type(mut self, gl_type: u32) -> Self {
+        self.header.gl_type = gl_type;
+        self
+    }
+
+    /// Sets `glTypeSize`, see [`KtxInfo::gl_type_size`](../header/trait.KtxInfo.html#tymethod.gl_type_size).
+    pub fn gl_type_size(mut self, gl_type_size: u32) -> Self {
+        self.header.gl_type_size = gl_type_size;
+        self
+    }
+
+    /// Sets `glFormat`, see [`KtxInfo::gl_format`](../header/trait.KtxInfo.html#tymethod.gl_format).
+    pub fn gl_format(mut self, gl_format: u32) -> Self {
+        self.header.gl_format = gl_format;
+        self
+    }
+
+    /// Sets `glInternalFormat`, see
+    /// [`KtxInfo::gl_internal_format`](../header/trait.KtxInfo.html#tymethod.gl_internal_format).
+    pub fn gl_internal_format(mut self, gl_internal_format: u32) -> Self {
+        self.header.gl_internal_format = gl_internal_format;
+        self
+    }
+
+    /// Sets `glBaseInternalFormat`, see
+    /// [`KtxInfo::gl_base_internal_format`](../header/trait.KtxInfo.html#tymethod.gl_base_internal_format).
+    pub fn gl_base_internal_format(mut self, gl_base_internal_format: u32) -> Self {
+        self.header.gl_base_internal_format = gl_base_internal_format;
+        self
+    }
+
+    /// Sets `pixelWidth`, see [`KtxInfo::pixel_width`](../header/trait.KtxInfo.html#tymethod.pixel_width).
+    pub fn pixel_width(mut self, pixel_width: u32) -> Self {
+        self.header.pixel_width = pixel_width;
+        self
+    }
+
+    /// Sets `pixelHeight`, see [`KtxInfo::pixel_height`](../header/trait.KtxInfo.html#tymethod.pixel_height).
+    pub fn pixel_height(mut self, pixel_height: u32) -> Self {
+        self.header.pixel_height = pixel_height;
+        self
+    }
+
+    /// Sets `pixelDepth`, see [`KtxInfo::pixel_depth`](../header/trait.KtxInfo.html#tymethod.pixel_depth).
+    pub fn pixel_depth(mut self, pixel_depth: u32) -> Self {
+        self.header.pixel_depth = pixel_depth;
+        self
+    }
+
+    /// Sets `numberOfArrayElements`, see
+    /// [`KtxInfo::array_elements`](../header/trait.KtxInfo.html#tymethod.array_elements).
+    pub fn array_elements(mut self, array_elements: u32) -> Self {
+        self.header.array_elements = array_elements;
+        self
+    }
+
+    /// Sets `numberOfFaces`, see [`KtxInfo::faces`](../header/trait.KtxInfo.html#tymethod.faces).
+    pub fn faces(mut self, faces: u32) -> Self {
+        self.header.faces = faces;
+        self
+    }
+
+    /// Sets `numberOfMipmapLevels`, see
+    /// [`KtxInfo::mipmap_levels`](../header/trait.KtxInfo.html#tymethod.mipmap_levels).
+    pub fn mipmap_levels(mut self, mipmap_levels: u32) -> Self {
+        self.header.mipmap_levels = mipmap_levels;
+        self
+    }
+
+    /// Appends a key/value metadata pair.
+    ///
+    /// String values should include a NUL terminator.
+    pub fn key_value(mut self, key: &str, value: impl AsRef<[u8]>) -> Self {
+        let value = value.as_ref();
+        let pair_len = key.len() + 1 + value.len();
+        self.key_value_data
+            .extend_from_slice(&(pair_len as u32).to_le_bytes());
+        self.key_value_data.extend_from_slice(key.as_bytes());
+        self.key_value_data.push(0);
+        self.key_value_data.extend_from_slice(value);
+        self.key_value_data
+            .resize(padded_len(self.key_value_data.len()), 0);
+        self.header.bytes_of_key_value_data = self.key_value_data.len() as u32;
+        self
+    }
+
+    /// Appends `KTXorientation` metadata, see [`Orientation`](../key_value/struct.Orientation.html).
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{format::*, key_value::{Direction, Orientation}, KtxBuilder};
+    ///
+    /// let ktx_data = KtxBuilder::new()
+    ///     .gl_type(UNSIGNED_BYTE)
+    ///     .gl_format(RED)
+    ///     .gl_internal_format(R8)
+    ///     .gl_base_internal_format(RED)
+    ///     .pixel_width(4)
+    ///     .pixel_height(1)
+    ///     .level(vec![0; 4])
+    ///     .orientation(Orientation { s: Direction::Right, t: Direction::Up, r: None })
+    ///     .to_vec()?;
+    ///
+    /// let image = ktx::Ktx::new(ktx_data.as_slice());
+    /// assert_eq!(image.orientation().unwrap().t, Direction::Up);
+    /// # Ok::<(), ktx::write::BuildError>(())
+    /// ```
+    pub fn orientation(self, orientation: Orientation) -> Self {
+        self.key_value(KTX_ORIENTATION, format!("{orientation}\0"))
+    }
+
+    /// Appends `KTXswizzle` metadata, the source of each of the red, green, blue & alpha channels.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{key_value::Channel::*, KtxBuilder};
+    ///
+    /// // single channel mask sampled as rrrr
+    /// let builder = KtxBuilder::new().swizzle([Red, Red, Red, Red]);
+    /// ```
+    pub fn swizzle(self, swizzle: [Channel; 4]) -> Self {
+        let [r, g, b, a] = swizzle.map(Channel::to_byte);
+        self.key_value(KTX_SWIZZLE, [r, g, b, a, 0])
+    }
+
+    /// Appends texture data for the next level, starting at level 0.
+    ///
+    /// Level data should contain all array elements & faces, in the same layout
+    /// as provided by [`Ktx::textures`](../slice/struct.Ktx.html#method.textures).
+    pub fn level(mut self, data: impl Into<Vec<u8>>) -> Self {
+        self.levels.push(data.into());
+        self
+    }
+
+    /// Appends texture data for the next level of a cubemap from the 6 face images, in order:
+    /// +X, -X, +Y, -Y, +Z, -Z. Sets `faces` to `6`.
+    ///
+    /// Faces are stored as a single level, written with the per-face `imageSize` of
+    /// non-array cubemaps.
+    ///
+    /// Errors if the faces are not all the same length, or the `pixel_width` & `pixel_height`
+    /// set so far are not square.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{format::*, Ktx, KtxBuilder, KtxInfo};
+    ///
+    /// let faces = [[0; 16], [1; 16], [2; 16], [3; 16], [4; 16], [5; 16]];
+    /// let ktx_data = KtxBuilder::new()
+    ///     .gl_type(UNSIGNED_BYTE)
+    ///     .gl_format(RGBA)
+    ///     .gl_internal_format(RGBA8)
+    ///     .gl_base_internal_format(RGBA)
+    ///     .pixel_width(2)
+    ///     .pixel_height(2)
+    ///     .add_cube_level(faces)?
+    ///     .to_vec()?;
+    ///
+    /// let image = Ktx::new(ktx_data);
+    /// assert!(image.is_cubemap());
+    /// assert_eq!(image.sub_image(0, 0, 3, 0), &[3; 16]);
+    /// # Ok::<(), ktx::write::BuildError>(())
+    /// ```
+    pub fn add_cube_level(mut self, faces: [impl AsRef<[u8]>; 6]) -> Result<Self, BuildError> {
+        let header = self.header;
+        if header.pixel_width != header.pixel_height || header.pixel_depth != 0 {
+            return Err(BuildError::NonSquareFaces {
+                width: header.pixel_width,
+                height: header.pixel_height,
+                depth: header.pixel_depth,
+            });
+        }
+        let face_len = faces[0].as_ref().len();
+        if faces.iter().any(|face| face.as_ref().len() != face_len) {
+            return Err(BuildError::UnequalFaces {
+                level: self.levels.len() as u32,
+            });
+        }
+
+        let mut level = Vec::with_capacity(face_len * 6);
+        for face in &faces {
+            level.extend_from_slice(face.as_ref());
+        }
+        self.header.faces = 6;
+        self.levels.push(level);
+        Ok(self)
+    }
+
+    /// Generates the full mipmap chain from level 0 & sets `mipmap_levels`.
+    ///
+    /// Requires a single level, non-array, non-cubemap, 2D texture with `glType`
+    /// `UNSIGNED_BYTE` & `glFormat` `RGB` or `RGBA`.
+    ///
+    /// `SRGB8` & `SRGB8_ALPHA8` colour channels are filtered in linear space.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{format::*, write::MipFilter, KtxBuilder, KtxInfo};
+    ///
+    /// let ktx_data = KtxBuilder::new()
+    ///     .gl_type(UNSIGNED_BYTE)
+    ///     .gl_format(RGBA)
+    ///     .gl_internal_format(RGBA8)
+    ///     .gl_base_internal_format(RGBA)
+    ///     .pixel_width(4)
+    ///     .pixel_height(2)
+    ///     .level(vec![255; 4 * 2 * 4])
+    ///     .generate_mipmaps(MipFilter::Box)?
+    ///     .to_vec()?;
+    ///
+    /// assert_eq!(ktx::Ktx::new(ktx_data).mipmap_levels(), 3);
+    /// # Ok::<(), ktx::write::BuildError>(())
+    /// ```
+    pub fn generate_mipmaps(mut self, filter: MipFilter) -> Result<Self, BuildError> {
+        let header = self.header;
+        let channels = match (header.gl_type, header.gl_format) {
+            (format::UNSIGNED_BYTE, format::RGBA) => 4,
+            (format::UNSIGNED_BYTE, format::RGB) => 3,
+            _ => return Err(BuildError::MipmapsUnsupported),
+        };
+        let srgb = matches!(
+            header.gl_internal_format,
+            format::SRGB8 | format::SRGB8_ALPHA8
+        );
+        // sample values scaled to 0..=255, sRGB colour channels decoded to linear
+        let decoded: Vec<f32> = (0..=255_u8)
+            .map(|v| match srgb {
+                true => srgb_to_linear(f32::from(v) / 255.0) * 255.0,
+                false => f32::from(v),
+            })
+            .collect();
+        if header.pixel_depth > 0 || header.array_elements > 0 || header.faces > 1 {
+            return Err(BuildError::MipmapsUnsupported);
+        }
+        if self.levels.len() != 1 {
+            return Err(BuildError::LevelCount {
+                expected: 1,
+                actual: self.levels.len(),
+            });
+        }
+        let expected = expected_level_len(&self, 0)
+            .map_err(|_| BuildError::DimensionsOverflow)?
+            .unwrap_or_default();
+        if self.levels[0].len() != expected {
+            return Err(BuildError::LevelSize {
+                level: 0,
+                expected,
+                actual: self.levels[0].len(),
+            });
+        }
+
+        for level in 1..self.level_count_for_full_chain() {
+            let (w, h, _) = self.level_dimensions(level - 1);
+            let (mip_w, mip_h, _) = self.level_dimensions(level);
+            let (w, h) = (w as usize, h as usize);
+            let (mip_w, mip_h) = (mip_w as usize, mip_h as usize);
+            let src = self.levels.last().expect("level");
+            let src_stride = padded_len(w * channels);
+            let mut mip = Vec::with_capacity(padded_len(mip_w * channels) * mip_h);
+
+            for y in 0..mip_h {
+                for x in 0..mip_w {
+                    for c in 0..channels {
+                        // alpha is always linear
+                        let linearize = srgb && c < 3;
+                        let sample = |sx: usize, sy: usize| {
+                            let v = src[sy.min(h - 1) * src_stride + sx.min(w - 1) * channels + c];
+                            match linearize {
+                                true => decoded[v as usize],
+                                false => f32::from(v),
+                            }
+                        };
+                        let value = match filter {
+                            MipFilter::Box => {
+                                let sum = sample(2 * x, 2 * y)
+                                    + sample(2 * x + 1, 2 * y)
+                                    + sample(2 * x, 2 * y + 1)
+                                    + sample(2 * x + 1, 2 * y + 1);
+                                sum / 4.0
+                            }
+                            MipFilter::Triangle => {
+                                const WEIGHTS: [f32; 4] = [1.0, 3.0, 3.0, 1.0];
+                                let mut sum = 0.0;
+                                for (dy, wy) in WEIGHTS.iter().enumerate() {
+                                    // -1..=2 offsets, clamped to the image edges
+                                    let sy = (2 * y + dy).saturating_sub(1);
+                                    for (dx, wx) in WEIGHTS.iter().enumerate() {
+                                        let sx = (2 * x + dx).saturating_sub(1);
+                                        sum += wx * wy * sample(sx, sy);
+                                    }
+                                }
+                                sum / 64.0
+                            }
+                        };
+                        let value = match linearize {
+                            true => linear_to_srgb(value / 255.0) * 255.0,
+                            false => value,
+                        };
+                        mip.push(value.round() as u8);
+                    }
+                }
+                mip.resize(padded_len(mip.len()), 0);
+            }
+
+            self.levels.push(mip);
+        }
+
+        self.header.mipmap_levels = self.levels.len() as u32;
+        Ok(self)
+    }
+
+    /// Validates the levels are consistent with the header & returns complete KTX data.
+    pub fn to_vec(&self) -> Result<Vec<u8>, BuildError> {
+        self.validate()?;
+
+        let header = self.header;
+        let data_len: usize = self.levels.iter().map(|l| 4 + padded_len(l.len())).sum();
+        let mut out = Vec::with_capacity(64 + self.key_value_data.len() + data_len);
+        out.extend_from_slice(&header.to_bytes());
+        out.extend_from_slice(&self.key_value_data);
+
+        for (index, level) in self.levels.iter().enumerate() {
+            let image_size = format::image_size_field(level.len(), &header).ok_or(
+                BuildError::LevelTooLarge {
+                    level: index as u32,
+                },
+            )?;
+            out.extend_from_slice(&image_size.to_le_bytes());
+            out.extend_from_slice(level);
+            out.resize(out.len() + padded_len(level.len()) - level.len(), 0);
+        }
+        Ok(out)
+    }
+
+    /// Writes the header & key/value data, returning a [`KtxEncoder`](struct.KtxEncoder.html)
+    /// to stream texture level data to `w` as it is produced. Levels already added to the
+    /// builder are written first.
+    ///
+    /// As the header is written immediately, `mipmap_levels`, `faces` & `array_elements`
+    /// must be set before calling this. Errors with `InvalidInput`, wrapping a
+    /// [`BuildError`](enum.BuildError.html), if the cubemap settings are invalid.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{format::*, Ktx, KtxBuilder, KtxInfo};
+    ///
+    /// let mut encoder = KtxBuilder::new()
+    ///     .gl_type(UNSIGNED_BYTE)
+    ///     .gl_format(RGBA)
+    ///     .gl_internal_format(RGBA8)
+    ///     .gl_base_internal_format(RGBA)
+    ///     .pixel_width(2)
+    ///     .pixel_height(2)
+    ///     .faces(6)
+    ///     .encoder(Vec::new())?;
+    ///
+    /// encoder.begin_level(6 * 2 * 2 * 4)?;
+    /// for face in 0..6 {
+    ///     // e.g. render each face in turn
+    ///     encoder.write_face(&[face; 2 * 2 * 4])?;
+    /// }
+    /// encoder.end_level()?;
+    ///
+    /// let image = Ktx::new(encoder.finish()?);
+    /// assert!(image.is_cubemap());
+    /// # Ok::<(), std::io::Error>(())
+    /// ```
+    pub fn encoder<W: io::Write>(self, mut w: W) -> io::Result<KtxEncoder<W>> {
+        self.validate_faces().map_err(invalid_input)?;
+        w.write_all(&self.header.to_bytes())?;
+        w.write_all(&self.key_value_data)?;
+        let mut encoder = KtxEncoder {
+            header: self.header,
+            out: w,
+            next_level: 0,
+            level: None,
+        };
+        for level in &self.levels {
+            encoder.write_level(level)?;
+        }
+        Ok(encoder)
+    }
+
+    fn validate(&self) -> Result<(), BuildError> {
+        self.validate_faces()?;
+
+        let expected_levels = self.header.mipmap_levels.max(1);
+        if self.levels.len() != expected_levels as usize {
+            return Err(BuildError::LevelCount {
+                expected: expected_levels,
+                actual: self.levels.len(),
+            });
+        }
+
+        for (level, data) in self.levels.iter().enumerate() {
+            check_level_len(&self.header, level as u32, data.len())?;
+        }
+        Ok(())
+    }
+
+    fn validate_faces(&self) -> Result<(), BuildError> {
+        let header = &self.header;
+        match header.faces {
+            1 => {}
+            6 if header.internal_format().is_paletted() => return Err(BuildError::PalettedCubemap),
+            6 if header.pixel_width != header.pixel_height || header.pixel_depth != 0 => {
+                return Err(BuildError::NonSquareFaces {
+                    width: header.pixel_width,
+                    height: header.pixel_height,
+                    depth: header.pixel_depth,
+                });
+            }
+            6 => {}
+            faces => return Err(BuildError::FaceCount { faces }),
+        }
+        Ok(())
+    }
+}
+
+/// Decodes a `0..=1` sRGB value to linear.
+fn srgb_to_linear(v: f32) -> f32 {
+    match v <= 0.04045 {
+        true => v / 12.92,
+        false => ((v + 0.055) / 1.055).powf(2.4),
+    }
+}
+
+/// Encodes a `0..=1` linear value to sRGB.
+fn linear_to_srgb(v: f32) -> f32 {
+    match v <= 0.003_130_8 {
+        true => v * 12.92,
+        false => 1.055 * v.powf(1.0 / 2.4) - 0.055,
+    }
+}
+
+/// Checks a complete level length is consistent with the header.
+///
+/// Non-array cubemap faces must be a multiple of 4 bytes, so no `cubePadding` is needed
+/// & the level data read back is exactly the level data written.
+fn check_level_len(header: &KtxHeader, level: u32, len: usize) -> Result<(), BuildError> {
+    let images = (header.faces.max(1) as usize)
+        .checked_mul(header.array_elements.max(1) as usize)
+        .ok_or(BuildError::DimensionsOverflow)?;
+    if !len.is_multiple_of(images) {
+        return Err(BuildError::UnequalFaces { level });
+    }
+    let expected = expected_level_len(header, level).map_err(|_| BuildError::DimensionsOverflow)?;
+    if let Some(expected) = expected {
+        if len != expected {
+            return Err(BuildError::LevelSize {
+                level,
+                expected,
+                actual: len,
+            });
+        }
+    }
+    if format::has_face_image_size(header) && !(len / 6).is_multiple_of(4) {
+        return Err(BuildError::UnalignedFaces { level });
+    }
+    Ok(())
+}
+
+#[cfg(feature = "hash")]
+impl KtxBuilder {
+    /// Appends `KTXhash` metadata containing the blake3 hash of all texture level data
+    /// added so far, so should be called after all levels are added.
+    ///
+    /// Level data is hashed as stored, in level order, excluding `imageSize` values & padding.
+    /// Verify with [`Ktx::verify_hash`](../slice/struct.Ktx.html#method.verify_hash).
+    ///
+    /// Requires the `hash` feature.
+    ///
+    /// # Example
+    /// ```
+    /// use ktx::{format::*, Ktx, KtxBuilder};
+    ///
+    /// let ktx_data = KtxBuilder::new()
+    ///     .gl_type(UNSIGNED_BYTE)
+    ///     .gl_format(RED)
+    ///     .gl_internal_format(R8)
+    ///     .gl_base_internal_format(RED)
+    ///     .pixel_width(4)
+    ///     .pixel_height(1)
+    ///     .level(vec![1, 2, 3, 4])
+    ///     .content_hash()
+    ///     .to_vec()?;
+    /// assert_eq!(Ktx::new(ktx_data).verify_hash(), Some(true));
+    /// # Ok::<(), ktx::write::BuildError>(())
+    /// ```
+    pub fn content_hash(self) -> Self {
+        let mut hasher = blake3::Hasher::new();
+        for level in &self.levels {
+            hasher.update(level);
+        }
+        let value = crate::key_value::hash_value(&hasher.finalize());
+        self.key_value(crate::key_value::KTX_HASH, value)
+    }
+}
+
+#[cfg(feature = "image")]
+impl KtxBuilder {
+    /// Decodes a PNG or JPEG image file into a builder for an uncompressed `RGBA8`, or
+    /// `SRGB8_ALPHA8`, texture with all levels provided.
+    ///
+    /// Use [`KtxBuilder::to_vec`](#method.to_vec) to produce the KTX data.
+    ///
+    /// # Example
+    /// ```no_run
+    /// use ktx::{write::ImageOptions, KtxBuilder};
+    ///
+    /// let builder = KtxBuilder::from_image_path("logo.png", ImageOptions::new().mipmaps(true))?;
+    /// std::fs::write("logo.ktx", builder.to_vec()?)?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    pub fn from_image_path(
+        path: impl AsRef<std::path::Path>,
+        options: ImageOptions,
+    ) -> Result<Self, FromImageError> {
+        let image = image::open(path)?.into_rgba8();
+        let (width, height) = image.dimensions();
+
+        let internal_format = match options.srgb {
+            true => format::SRGB8_ALPHA8,
+            false => format::RGBA8,
+        };
+        let builder = Self::new()
+            .gl_type(format::UNSIGNED_BYTE)
+            .gl_format(format::RGBA)
+            .gl_internal_format(internal_format)
+            .gl_base_internal_format(format::RGBA)
+            .pixel_width(width)
+            .pixel_height(height)
+            .level(image.into_raw());
+
+        Ok(match options.mipmaps {
+            true => builder.generate_mipmaps(MipFilter::Triangle)?,
+            false => builder,
+        })
+    }
+}
+
+/// Options for [`KtxBuilder::from_image_path`](struct.KtxBuilder.html#method.from_image_path).
+#[cfg(feature = "image")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ImageOptions {
+    srgb: bool,
+    mipmaps: bool,
+}
+
+#[cfg(feature = "image")]
+impl Default for ImageOptions {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "image")]
+impl ImageOptions {
+    /// Returns default options: sRGB colour space without mipmaps.
+    pub fn new() -> Self {
+        Self {
+            srgb: true,
+            mipmaps: false,
+        }
+    }
+
+    /// Sets whether the image is in the sRGB colour space, using `SRGB8_ALPHA8`, otherwise `RGBA8`.
+    /// Default `true`.
+    pub fn srgb(mut self, srgb: bool) -> Self {
+        self.srgb = srgb;
+        self
+    }
+
+    /// Sets whether to generate a full mipmap chain from the image. Default `false`.
+    pub fn mipmaps(mut self, mipmaps: bool) -> Self {
+        self.mipmaps = mipmaps;
+        self
+    }
+}
+
+/// Error creating a builder with
+/// [`KtxBuilder::from_image_path`](struct.KtxBuilder.html#method.from_image_path).
+#[cfg(feature = "image")]
+#[derive(Debug)]
+pub enum FromImageError {
+    /// Opening or decoding the image failed.
+    Image(image::ImageError),
+    /// Building the texture, e.g. generating mipmaps, failed.
+    Build(BuildError),
+}
+
+#[cfg(feature = "image")]
+impl From<image::ImageError> for FromImageError {
+    #[inline]
+    fn from(err: image::ImageError) -> Self {
+        Self::Image(err)
+    }
+}
+
+#[cfg(feature = "image")]
+impl From<BuildError> for FromImageError {
+    #[inline]
+    fn from(err: BuildError) -> Self {
+        Self::Build(err)
+    }
+}
+
+#[cfg(feature = "image")]
+impl fmt::Display for FromImageError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Image(err) => err.fmt(f),
+            Self::Build(err) => err.fmt(f),
+        }
+    }
+}
+
+#[cfg(feature = "image")]
+impl Error for FromImageError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Image(err) => Some(err),
+            Self::Build(err) => Some(err),
+        }
+    }
+}
+
+/// Streaming KTX writer, writing texture level data as it is produced, see
+/// [`KtxBuilder::encoder`](struct.KtxBuilder.html#method.encoder).
+///
+/// Levels may be written whole with [`write_level`](#method.write_level), or a face at a time
+/// with [`begin_level`](#method.begin_level), [`write_face`](#method.write_face) &
+/// [`end_level`](#method.end_level). `imageSize` & `mipPadding` are written by the encoder.
+/// Non-array cubemap faces must be a multiple of 4 bytes, so `cubePadding` is always empty.
+///
+/// Provides [`KtxInfo`](../header/trait.KtxInfo.html).
+#[derive(Debug)]
+pub struct KtxEncoder<W> {
+    header: KtxHeader,
+    out: W,
+    /// Number of levels begun.
+    next_level: u32,
+    /// Level currently being written a face at a time.
+    level: Option<LevelProgress>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct LevelProgress {
+    face_len: usize,
+    faces_remaining: usize,
+    mip_padding: usize,
+}
+
+impl<W> AsRef<KtxHeader> for KtxEncoder<W> {
+    #[inline]
+    fn as_ref(&self) -> &KtxHeader {
+        &self.header
+    }
+}
+
+impl<W: io::Write> KtxEncoder<W> {
+    /// Begins the next level, starting at level 0, writing its `imageSize`.
+    ///
+    /// `len` is the length of the complete level, including all array elements & faces,
+    /// which must then be written with [`write_face`](#method.write_face).
+    ///
+    /// Errors if a level is already in progress, all levels have been written, or `len`
+    /// is inconsistent with the header.
+    pub fn begin_level(&mut self, len: usize) -> io::Result<()> {
+        if self.level.is_some() {
+            return Err(invalid_input("level already in progress"));
+        }
+        let level = self.next_level;
+        let levels = self.header.mipmap_levels.max(1);
+        if level >= levels {
+            return Err(invalid_input(BuildError::LevelCount {
+                expected: levels,
+                actual: level as usize + 1,
+            }));
+        }
+
+        check_level_len(&self.header, level, len).map_err(invalid_input)?;
+
+        let images = self.header.faces.max(1) as usize * self.header.array_elements.max(1) as usize;
+        let face_len = len / images;
+        let image_size = format::image_size_field(len, &self.header)
+            .ok_or_else(|| invalid_input(BuildError::LevelTooLarge { level }))?;
+        self.out.write_all(&image_size.to_le_bytes())?;
+
+        self.next_level += 1;
+        self.level = Some(LevelProgress {
+            face_len,
+            faces_remaining: images,
+            mip_padding: padded_len(len) - len,
+        });
+        Ok(())
+    }
+
+    /// Writes the next face of the level in progress, see [`begin_level`](#method.begin_level).
+    ///
+    /// Faces are written in order: +X, -X, +Y, -Y, +Z, -Z. Non-cubemap textures have a single
+    /// face per array element. For array textures each array element's faces are written
+    /// in turn.
+    ///
+    /// Errors if no level is in progress, all faces of the level have been written, or the
+    /// data length is not the level length divided by the number of faces & array elements.
+    pub fn write_face(&mut self, data: &[u8]) -> io::Result<()> {
+        let level = self
+            .level
+            .as_mut()
+            .ok_or_else(|| invalid_input("no level in progress"))?;
+        if level.faces_remaining == 0 {
+            return Err(invalid_input("all faces of the level have been written"));
+        }
+        if data.len() != level.face_len {
+            return Err(invalid_input(format!(
+                "expected face to be {} bytes, got {}",
+                level.face_len,
+                data.len()
+            )));
+        }
+
+        self.out.write_all(data)?;
+        level.faces_remaining -= 1;
+        Ok(())
+    }
+
+    /// Ends the level in progress, writing `mipPadding`.
+    ///
+    /// Errors if no level is in progress, or faces are missing.
+    pub fn end_level(&mut self) -> io::Result<()> {
+        let level = self
+            .level
+            .ok_or_else(|| invalid_input("no level in progress"))?;
+        if level.faces_remaining > 0 {
+            return Err(invalid_input(format!(
+                "{} faces of the level have not been written",
+                level.faces_remaining
+            )));
+        }
+        self.out.write_all(&[0; 3][..level.mip_padding])?;
+        self.level = None;
+        Ok(())
+    }
+
+    /// Writes the next complete level, starting at level 0. Level data should contain all
+    /// array elements & faces, in the same layout as provided by
+    /// [`Ktx::textures`](../slice/struct.Ktx.html#method.textures).
+    pub fn write_level(&mut self, data: &[u8]) -> io::Result<()> {
+        self.begin_level(data.len())?;
+        // faces may be empty, e.g. a zero length level of an unknown format
+        let (face_len, faces) = self
+            .level
+            .map_or((0, 0), |level| (level.face_len, level.faces_remaining));
+        for face in 0..faces {
+            self.write_face(&data[face * face_len..][..face_len])?;
+        }
+        self.end_level()
+    }
+
+    /// Flushes & returns the writer after checking all levels have been written.
+    pub fn finish(mut self) -> io::Result<W> {
+        if self.level.is_some() {
+            return Err(invalid_input("level in progress"));
+        }
+        let levels = self.header.mipmap_levels.max(1);
+        if self.next_level != levels {
+            return Err(invalid_input(BuildError::LevelCount {
+                expected: levels,
+                actual: self.next_level as usize,
+            }));
+        }
+        self.out.flush()?;
+        Ok(self.out)
+    }
+}
+
+#[inline]
+fn invalid_input(err: impl Into<Box<dyn Error + Send + Sync>>) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidInput, err)
+}
+
+/// Returns KTX data for a single level `RGBA8` texture from tightly packed pixels.
+///
+/// # Panics
+///
+/// `width` or `height` is `0`, or pixel data length is not `width * height * 4`.
+///
+/// # Example
+/// ```
+/// use ktx::KtxInfo;
+///
+/// let ktx_data = ktx::from_rgba8(2, 2, &[255; 2 * 2 * 4]);
+/// assert_eq!(ktx::Ktx::new(ktx_data).pixel_width(), 2);
+/// ```
+pub fn from_rgba8(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    from_pixels(width, height, pixels, 4, format::RGBA, format::RGBA8)
+}
+
+/// Returns KTX data for a single level `RGB8` texture from tightly packed pixels.
+/// Rows are padded to 4 bytes.
+///
+/// # Panics
+///
+/// `width` or `height` is `0`, or pixel data length is not `width * height * 3`.
+pub fn from_rgb8(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    from_pixels(width, height, pixels, 3, format::RGB, format::RGB8)
+}
+
+/// Returns KTX data for a single level `R8` texture from tightly packed pixels.
+/// Rows are padded to 4 bytes.
+///
+/// # Panics
+///
+/// `width` or `height` is `0`, or pixel data length is not `width * height`.
+pub fn from_r8(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    from_pixels(width, height, pixels, 1, format::RED, format::R8)
+}
+
+fn from_pixels(
+    width: u32,
+    height: u32,
+    pixels: &[u8],
+    channels: usize,
+    gl_format: u32,
+    gl_internal_format: u32,
+) -> Vec<u8> {
+    assert!(width > 0 && height > 0, "width & height must be non-zero");
+    let row_len = width as usize * channels;
+    assert!(
+        row_len.checked_mul(height as usize) == Some(pixels.len()),
+        "invalid pixel data length"
+    );
+
+    let mut level = Vec::with_capacity(padded_len(row_len) * height as usize);
+    for row in pixels.chunks_exact(row_len) {
+        level.extend_from_slice(row);
+        level.resize(padded_len(level.len()), 0);
+    }
+
+    KtxBuilder::new()
+        .gl_type(format::UNSIGNED_BYTE)
+        .gl_format(gl_format)
+        .gl_internal_format(gl_internal_format)
+        .gl_base_internal_format(gl_format)
+        .pixel_width(width)
+        .pixel_height(height)
+        .level(level)
+        .to_vec()
+        .expect("valid level data")
+}
+
+/// Filter used to downsample mipmap levels, see
+/// [`KtxBuilder::generate_mipmaps`](struct.KtxBuilder.html#method.generate_mipmaps).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MipFilter {
+    /// Averages each 2x2 block of pixels.
+    Box,
+    /// Weights a 4x4 neighbourhood of pixels with a tent filter, giving smoother results.
+    Triangle,
+}
+
+/// Error building KTX data with a [`KtxBuilder`](struct.KtxBuilder.html).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BuildError {
+    /// The number of levels does not match `mipmap_levels` (or `1` when `mipmap_levels` is `0`).
+    LevelCount { expected: u32, actual: usize },
+    /// Level data length does not match the length expected from the header dimensions & format.
+    LevelSize {
+        level: u32,
+        expected: usize,
+        actual: usize,
+    },
+    /// Mipmap generation is not supported for the texture format or layout, see
+    /// [`KtxBuilder::generate_mipmaps`](struct.KtxBuilder.html#method.generate_mipmaps).
+    MipmapsUnsupported,
+    /// `faces` is not `1` or `6`.
+    FaceCount { faces: u32 },
+    /// Cubemap faces are not square, `pixel_width` must equal `pixel_height`
+    /// & `pixel_depth` must be `0`.
+    NonSquareFaces { width: u32, height: u32, depth: u32 },
+    /// Cubemaps may not use paletted `GL_PALETTE*` internal formats.
+    PalettedCubemap,
+    /// Level data cannot be split into equally sized faces & array elements.
+    UnequalFaces { level: u32 },
+    /// Non-array cubemap faces are not a multiple of 4 bytes, which would require
+    /// `cubePadding` between faces.
+    UnalignedFaces { level: u32 },
+    /// The header dimensions, `faces` & `array_elements` give a level length too large to
+    /// address.
+    DimensionsOverflow,
+    /// Level data is too large for its `u32` `imageSize`.
+    LevelTooLarge { level: u32 },
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::LevelCount { expected, actual } => {
+                write!(f, "expected {expected} texture levels, got {actual}")
+            }
+            Self::LevelSize {
+                level,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "expected texture level {level} to be {expected} bytes, got {actual}"
+            ),
+            Self::MipmapsUnsupported => {
+                f.write_str("mipmap generation is not supported for this texture")
+            }
+            Self::FaceCount { faces } => write!(f, "expected 1 or 6 faces, got {faces}"),
+            Self::NonSquareFaces {
+                width,
+                height,
+                depth,
+            } => write!(
+                f,
+                "cubemap faces must be square & 2D, got {width}x{height}x{depth}"
+            ),
+            Self::PalettedCubemap => f.write_str("cubemaps may not use paletted formats"),
+            Self::UnequalFaces { level } => {
+                write!(f, "texture level {level} cannot be split into equal faces")
+            }
+            Self::UnalignedFaces { level } => write!(
+                f,
+                "texture level {level} cubemap faces are not a multiple of 4 bytes"
+            ),
+            Self::DimensionsOverflow => f.write_str("header dimensions overflow"),
+            Self::LevelTooLarge { level } => {
+                write!(f, "texture level {level} is too large for imageSize")
+            }
+        }
+    }
+}
+
+impl Error for BuildError {}
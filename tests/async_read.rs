@@ -0,0 +1,52 @@
+#![cfg(feature = "async")]
+use futures_executor::block_on;
+use futures_util::StreamExt;
+use ktx::*;
+
+#[test]
+fn stream_uffizi_6face_levels() {
+    let data: &[u8] = include_bytes!("uffizi_rgba16f_cube.ktx");
+    let levels: Vec<_> = block_on(async {
+        let decoder = AsyncDecoder::new(data).await.unwrap();
+        assert!(decoder.is_cubemap());
+        decoder.into_level_stream().collect().await
+    });
+
+    let ktx = Ktx::new(data);
+    assert_eq!(levels.len(), ktx.textures().len());
+    for (level, expected) in levels.into_iter().zip(ktx.textures()) {
+        assert_eq!(level.unwrap(), expected);
+    }
+}
+
+#[test]
+fn stream_truncated_logo_example() {
+    let data: &[u8] = include_bytes!("babg-bc3.ktx");
+    let levels: Vec<_> = block_on(async {
+        let decoder = AsyncDecoder::new(&data[..1000]).await.unwrap();
+        decoder.into_level_stream().collect().await
+    });
+
+    assert_eq!(levels.len(), 1);
+    assert_eq!(
+        levels[0].as_ref().unwrap_err().kind(),
+        std::io::ErrorKind::UnexpectedEof
+    );
+}
+
+#[test]
+fn stream_mip_padding() {
+    // unknown format with 3 & 1 byte levels, each followed by mipPadding
+    let data = KtxBuilder::new()
+        .mipmap_levels(2)
+        .level([1, 2, 3])
+        .level([4])
+        .to_vec()
+        .unwrap();
+    let levels: Vec<_> = block_on(async {
+        let decoder = AsyncDecoder::new(data.as_slice()).await.unwrap();
+        decoder.into_level_stream().collect().await
+    });
+    let levels: Vec<_> = levels.into_iter().map(Result::unwrap).collect();
+    assert_eq!(levels, [vec![1, 2, 3], vec![4]]);
+}
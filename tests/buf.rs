@@ -0,0 +1,30 @@
+#![cfg(feature = "bytes")]
+use bytes::{Buf, Bytes};
+use ktx::{buf::BufDecoder, *};
+
+#[test]
+fn buf_chunked_uffizi_6face() {
+    let data = Bytes::from_static(include_bytes!("uffizi_rgba16f_cube.ktx"));
+    let ktx = Ktx::new(&data[..]);
+
+    // level 0 data spans the chunks, the rest is in the second chunk
+    let chunks = data.slice(..1000).chain(data.slice(1000..));
+    let decoder = BufDecoder::new(chunks).unwrap();
+    assert_eq!(decoder.pixel_width(), ktx.pixel_width());
+    assert_eq!(decoder.faces(), 6);
+    assert_eq!(decoder.key_values().count(), 0);
+
+    let levels: Vec<_> = decoder.read_textures().collect();
+    assert_eq!(levels.len(), ktx.textures().len());
+    for (level, expected) in levels.into_iter().zip(ktx.textures()) {
+        assert_eq!(level.unwrap(), expected);
+    }
+
+    let truncated = BufDecoder::new(data.slice(..1000)).unwrap();
+    let mut levels = truncated.read_textures();
+    assert_eq!(
+        levels.next(),
+        Some(Err(KtxError::TruncatedLevel { level: 0 }))
+    );
+    assert_eq!(levels.next(), None);
+}
@@ -0,0 +1,30 @@
+#![cfg(feature = "capi")]
+use ktx::{capi::*, *};
+use std::ptr;
+
+#[test]
+fn capi_logo_example() {
+    let data = include_bytes!("babg-bc3.ktx");
+    let ktx = Ktx::new(data);
+    unsafe {
+        let texture = ktx_open_memory(data.as_ptr(), data.len());
+        assert!(!texture.is_null());
+
+        let header = ktx_header(texture);
+        assert_eq!(header.pixel_width, 260);
+        assert_eq!(header.gl_internal_format, ktx.gl_internal_format());
+        assert_eq!(ktx_level_count(texture), ktx.mipmap_levels());
+
+        let (mut level_data, mut len) = (ptr::null(), 0);
+        assert!(ktx_level(texture, 1, &mut level_data, &mut len));
+        assert_eq!(
+            std::slice::from_raw_parts(level_data, len),
+            ktx.texture_level(1)
+        );
+        assert!(!ktx_level(texture, 99, &mut level_data, &mut len));
+
+        ktx_free(texture);
+
+        assert!(ktx_open_memory(data.as_ptr(), 63).is_null());
+    }
+}
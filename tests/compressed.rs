@@ -0,0 +1,36 @@
+#![cfg(any(feature = "gzip", feature = "zstd"))]
+use ktx::*;
+
+const LOGO: &[u8] = include_bytes!("babg-bc3.ktx");
+
+#[cfg(feature = "gzip")]
+#[test]
+fn read_gzip_logo_example() {
+    use std::io::Write;
+
+    let mut gz = flate2::write::GzEncoder::new(vec![], flate2::Compression::fast());
+    gz.write_all(LOGO).unwrap();
+    let gz = gz.finish().unwrap();
+
+    let decoder = Decoder::new_gzip(gz.as_slice()).unwrap();
+    assert_eq!(decoder.pixel_width(), 260);
+    let levels: Vec<_> = decoder.read_textures().collect();
+    assert!(levels
+        .iter()
+        .map(Vec::as_slice)
+        .eq(Ktx::new(LOGO).textures()));
+}
+
+#[cfg(feature = "zstd")]
+#[test]
+fn read_zstd_logo_example() {
+    let zst = zstd::encode_all(LOGO, 1).unwrap();
+
+    let decoder = Decoder::new_zstd(zst.as_slice()).unwrap();
+    assert_eq!(decoder.pixel_width(), 260);
+    let levels: Vec<_> = decoder.read_textures().collect();
+    assert!(levels
+        .iter()
+        .map(Vec::as_slice)
+        .eq(Ktx::new(LOGO).textures()));
+}
@@ -0,0 +1,156 @@
+use ktx::{convert, *};
+
+#[test]
+fn astc_6x5_3d() {
+    // 13x5x2 pixels with 6x5 blocks, so 3x1 blocks per slice
+    let mut astc = vec![0x13, 0xAB, 0xA1, 0x5C, 6, 5, 1, 13, 0, 0, 5, 0, 0, 2, 0, 0];
+    let blocks: Vec<u8> = (0..6 * 16).map(|n| n as u8).collect();
+    astc.extend_from_slice(&blocks);
+
+    let ktx_data = convert::from_astc(&astc).unwrap();
+    let ktx = Ktx::new(ktx_data.as_slice());
+    assert_eq!(
+        ktx.gl_internal_format(),
+        format::COMPRESSED_RGBA_ASTC_6X5_KHR
+    );
+    assert_eq!(ktx.gl_base_internal_format(), format::RGBA);
+    assert_eq!(ktx.pixel_width(), 13);
+    assert_eq!(ktx.pixel_height(), 5);
+    assert_eq!(ktx.pixel_depth(), 2);
+    assert_eq!(ktx.mipmap_levels(), 1);
+    assert_eq!(ktx.texture_level(0), blocks);
+
+    assert_eq!(
+        convert::from_astc(&astc[..astc.len() - 1]),
+        Err(convert::ConvertError::InvalidInput(
+            "ASTC block data truncated"
+        ))
+    );
+
+    let mut zero_depth_blocks = astc.clone();
+    zero_depth_blocks[6] = 0;
+    assert_eq!(
+        convert::from_astc(&zero_depth_blocks),
+        Err(convert::ConvertError::InvalidInput(
+            "ASTC block dimensions must be non-zero"
+        ))
+    );
+
+    // 0xffffff^3 pixels
+    let mut huge = astc.clone();
+    huge[7..16].copy_from_slice(&[0xff; 9]);
+    assert_eq!(
+        convert::from_astc(&huge),
+        Err(convert::ConvertError::InvalidInput(
+            "ASTC block data truncated"
+        ))
+    );
+}
+
+#[cfg(feature = "pvr")]
+#[test]
+fn logo_example_pvr_round_trip() {
+    let ktx = include_ktx!("babg-bc3.ktx");
+    let pvr = convert::to_pvr(&ktx).unwrap();
+
+    assert_eq!(&pvr[..4], b"PVR\x03");
+    // BC3/DXT5 pixel format
+    assert_eq!(&pvr[8..16], &11_u64.to_le_bytes());
+    // height, width
+    assert_eq!(&pvr[24..32], &[200, 0, 0, 0, 4, 1, 0, 0]);
+
+    assert_eq!(convert::from_pvr(&pvr).unwrap(), ktx.data());
+}
+
+#[cfg(feature = "pvr")]
+#[test]
+fn uffizi_6face_pvr_round_trip() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let pvr = convert::to_pvr(&ktx).unwrap();
+
+    // 6 faces
+    assert_eq!(&pvr[40..44], &6_u32.to_le_bytes());
+    assert_eq!(convert::from_pvr(&pvr).unwrap(), ktx.data());
+}
+
+#[cfg(feature = "pvr")]
+#[test]
+fn pvr_rgb8_row_padding() {
+    let ktx_data = KtxBuilder::new()
+        .gl_type(format::UNSIGNED_BYTE)
+        .gl_format(format::RGB)
+        .gl_internal_format(format::SRGB8)
+        .gl_base_internal_format(format::RGB)
+        .pixel_width(3)
+        .pixel_height(2)
+        .level([
+            1, 2, 3, 4, 5, 6, 7, 8, 9, 0, 0, 0, 10, 11, 12, 13, 14, 15, 16, 17, 18, 0, 0, 0,
+        ])
+        .to_vec()
+        .unwrap();
+
+    let pvr = convert::to_pvr(&Ktx::new(ktx_data.as_slice())).unwrap();
+    // sRGB colour space
+    assert_eq!(&pvr[16..20], &1_u32.to_le_bytes());
+    // tightly packed rows
+    assert_eq!(&pvr[52..], (1..=18).collect::<Vec<u8>>());
+
+    assert_eq!(convert::from_pvr(&pvr).unwrap(), ktx_data);
+}
+
+#[cfg(feature = "pvr")]
+#[test]
+fn pvr_invalid() {
+    assert_eq!(
+        convert::from_pvr(b"not pvr data"),
+        Err(convert::ConvertError::InvalidInput(
+            "PVR data too short for a header"
+        ))
+    );
+
+    // RGBA8 header with u32::MAX width, height, surfaces & faces
+    let mut pvr = vec![0; 52];
+    let header = [
+        (0, 0x0352_5650),
+        (8, u32::from_le_bytes(*b"rgba")),
+        (12, 0x0808_0808),
+    ];
+    for (idx, val) in header {
+        pvr[idx..idx + 4].copy_from_slice(&u32::to_le_bytes(val));
+    }
+    for idx in [24, 28, 36, 40] {
+        pvr[idx..idx + 4].copy_from_slice(&u32::MAX.to_le_bytes());
+    }
+    assert_eq!(
+        convert::from_pvr(&pvr),
+        Err(convert::ConvertError::InvalidInput(
+            "PVR texture dimensions too large"
+        ))
+    );
+}
+
+#[cfg(feature = "pvr")]
+#[test]
+fn pvr_dxt1() {
+    let dxt1 = |internal_format| {
+        let ktx_data = KtxBuilder::new()
+            .gl_internal_format(internal_format)
+            .pixel_width(4)
+            .pixel_height(4)
+            .level([0; 8])
+            .to_vec()
+            .unwrap();
+        convert::to_pvr(&Ktx::new(ktx_data)).unwrap()
+    };
+    let rgb = dxt1(format::COMPRESSED_RGB_S3TC_DXT1_EXT);
+    assert_eq!(&rgb[8..16], &7_u64.to_le_bytes());
+    assert_eq!(rgb, dxt1(format::COMPRESSED_RGBA_S3TC_DXT1_EXT));
+
+    let ktx_data = convert::from_pvr(&rgb).unwrap();
+    let ktx = Ktx::new(ktx_data.as_slice());
+    assert_eq!(
+        ktx.gl_internal_format(),
+        format::COMPRESSED_RGB_S3TC_DXT1_EXT
+    );
+    assert_eq!(ktx.gl_base_internal_format(), format::RGB);
+}
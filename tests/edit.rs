@@ -0,0 +1,68 @@
+use ktx::{edit, format::*, *};
+
+#[test]
+fn edit_logo_example() {
+    let original = include_ktx!("babg-bc3.ktx");
+    let mut ktx = original.into_owned();
+
+    edit::set_key_value(&mut ktx, "a", [1]).unwrap();
+    edit::set_key_value(&mut ktx, "b", [2]).unwrap();
+    edit::set_key_value(&mut ktx, "a", [3]).unwrap();
+    assert_eq!(
+        ktx.key_values().collect::<Vec<_>>(),
+        [("b", &[2][..]), ("a", &[3][..])]
+    );
+    assert!(edit::remove_key_value(&mut ktx, "b").unwrap());
+    assert!(!edit::remove_key_value(&mut ktx, "b").unwrap());
+    assert_eq!(ktx.key_values().count(), 1);
+    assert!(ktx.textures().eq(original.textures()));
+
+    edit::replace_level(&mut ktx, 1, vec![7; original.texture_level(1).len()]).unwrap();
+    assert!(ktx.texture_level(1).iter().all(|b| *b == 7));
+    assert!(matches!(
+        edit::replace_level(&mut ktx, 1, vec![7; 3]),
+        Err(edit::EditError::Build(write::BuildError::LevelSize {
+            level: 1,
+            ..
+        }))
+    ));
+    assert_eq!(
+        edit::replace_level(&mut ktx, 8, vec![0; 16]),
+        Err(edit::EditError::InvalidLevel {
+            level: 8,
+            levels: 8
+        })
+    );
+
+    edit::drop_levels_below(&mut ktx, 100).unwrap();
+    assert_eq!(ktx.mipmap_levels(), 2);
+    assert_eq!(ktx.textures().len(), 2);
+    assert_eq!(ktx.texture_level(0), original.texture_level(0));
+
+    edit::patch_header(&mut ktx, |h| {
+        h.gl_internal_format(COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT)
+    })
+    .unwrap();
+    assert!(ktx.is_srgb());
+    assert_eq!(ktx.key_value("a"), Some(&[3][..]));
+}
+
+#[test]
+fn edit_invalid_data() {
+    let data = include_bytes!("babg-bc3.ktx");
+    let mut truncated = Ktx::new(data[..data.len() - 10].to_vec());
+    assert!(matches!(
+        edit::set_key_value(&mut truncated, "a", [1]),
+        Err(edit::EditError::Ktx(KtxError::TruncatedLevel { .. }))
+    ));
+
+    let mut malformed = include_ktx!("babg-bc3.ktx").into_owned();
+    edit::set_key_value(&mut malformed, "a", [1]).unwrap();
+    let mut data = malformed.to_vec();
+    data[64] = 0xff;
+    let mut malformed = Ktx::new(data);
+    assert!(matches!(
+        edit::set_key_value(&mut malformed, "b", [2]),
+        Err(edit::EditError::Ktx(KtxError::InvalidKeyValues))
+    ));
+}
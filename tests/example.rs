@@ -2,7 +2,7 @@ use blake2::{Blake2s256, Digest};
 use ktx::*;
 use std::{
     fs::File,
-    io::{self, BufReader},
+    io::{self, BufReader, Cursor},
     sync::Arc,
 };
 
@@ -29,6 +29,58 @@ fn include_logo_example() {
     assert_eq!(ktx.bytes_of_key_value_data(), 0, "bytes_of_key_value_data");
 }
 
+#[test]
+fn logo_example_format_info() {
+    let ktx = include_ktx!("babg-bc3.ktx");
+
+    assert!(ktx.is_compressed(), "is_compressed");
+    assert_eq!(ktx.block_dimensions(), (4, 4), "block_dimensions");
+    assert_eq!(
+        ktx.bytes_per_block_or_pixel(),
+        Some(16),
+        "bytes_per_block_or_pixel"
+    );
+    assert!(!ktx.is_cubemap(), "!is_cubemap");
+    assert!(!ktx.is_array(), "!is_array");
+    assert!(!ktx.is_srgb(), "!is_srgb");
+}
+
+#[test]
+fn srgb_variants() {
+    use ktx::format::*;
+
+    let dxt5 = InternalFormat(COMPRESSED_RGBA_S3TC_DXT5_EXT);
+    let srgb_dxt5 = InternalFormat(COMPRESSED_SRGB_ALPHA_S3TC_DXT5_EXT);
+    assert!(!dxt5.is_srgb());
+    assert!(srgb_dxt5.is_srgb());
+    assert_eq!(dxt5.to_srgb_variant(), Some(srgb_dxt5));
+    assert_eq!(srgb_dxt5.to_srgb_variant(), Some(srgb_dxt5));
+    assert_eq!(srgb_dxt5.to_linear_variant(), Some(dxt5));
+    assert_eq!(dxt5.to_linear_variant(), Some(dxt5));
+
+    let astc = InternalFormat(COMPRESSED_RGBA_ASTC_10X8_KHR);
+    let srgb_astc = InternalFormat(COMPRESSED_SRGB8_ALPHA8_ASTC_10X8_KHR);
+    assert_eq!(astc.to_srgb_variant(), Some(srgb_astc));
+    assert_eq!(srgb_astc.to_linear_variant(), Some(astc));
+    assert!(srgb_astc.is_srgb());
+
+    let rgtc = InternalFormat(COMPRESSED_RED_RGTC1);
+    assert_eq!(rgtc.to_srgb_variant(), None);
+    assert_eq!(rgtc.to_linear_variant(), None);
+    assert!(!rgtc.is_srgb());
+}
+
+#[test]
+fn static_logo_example() {
+    static LOGO: Ktx<&[u8]> = include_ktx!("babg-bc3.ktx");
+
+    assert_eq!(LOGO.pixel_width(), 260, "pixel_width");
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(LOGO.texture_level(7))),
+        LOGO_LEVEL_7_BLAKE
+    );
+}
+
 #[test]
 fn read_logo_example() -> io::Result<()> {
     let ktx = ktx::Decoder::new(BufReader::new(File::open("tests/babg-bc3.ktx")?))?;
@@ -62,6 +114,18 @@ fn owned_logo_example() {
     assert_eq!(ktx.pixel_height(), 200, "pixel_height");
 }
 
+#[test]
+fn owned_logo_example_into_owned() {
+    let data = include_bytes!("babg-bc3.ktx").to_vec();
+    let owned: Ktx<Vec<u8>> = Ktx::new(data.as_slice()).into_owned();
+    drop(data);
+
+    assert_eq!(owned.pixel_width(), 260, "pixel_width");
+    assert_eq!(owned.data(), &include_bytes!("babg-bc3.ktx")[..]);
+    assert_eq!(owned.textures().count(), 8, "owned.textures().count()");
+    assert_eq!(owned.into_inner(), include_bytes!("babg-bc3.ktx").to_vec());
+}
+
 const LOGO_LEVEL_0_BLAKE: &str = "17ae9dcdc7b7f8c38a66fe00ab92759fde35f74cde2aa52449c2ecbca835a51b";
 const LOGO_LEVEL_1_BLAKE: &str = "ae05372daa8bb0d45de4431db106e107266307e5a37b51825f10618a9605ee1b";
 const LOGO_LEVEL_2_BLAKE: &str = "52ed1d989b8dca91538d68e1077d303a95baf6068d240316bc18c3d8ef5625ea";
@@ -152,6 +216,88 @@ fn read_logo_example_textures() -> io::Result<()> {
     Ok(())
 }
 
+#[test]
+fn include_logo_example_textures_rev() {
+    let ktx = include_ktx!("babg-bc3.ktx");
+    let mut textures = ktx.textures().rev();
+    assert_eq!(textures.len(), 8);
+
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(textures.next().unwrap())),
+        LOGO_LEVEL_7_BLAKE
+    );
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(textures.next().unwrap())),
+        LOGO_LEVEL_6_BLAKE
+    );
+    assert_eq!(textures.len(), 6);
+
+    let mut textures = textures.rev();
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(textures.next().unwrap())),
+        LOGO_LEVEL_0_BLAKE
+    );
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(textures.nth(4).unwrap())),
+        LOGO_LEVEL_5_BLAKE
+    );
+    assert_eq!(textures.len(), 0);
+    assert_eq!(textures.next(), None);
+    assert_eq!(textures.next_back(), None);
+}
+
+#[test]
+fn read_logo_example_nth_texture() -> io::Result<()> {
+    let ktx = ktx::Decoder::new(BufReader::new(File::open("tests/babg-bc3.ktx")?))?;
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(ktx.nth_texture(0)?)),
+        LOGO_LEVEL_0_BLAKE
+    );
+
+    let ktx = ktx::Decoder::new(BufReader::new(File::open("tests/babg-bc3.ktx")?))?;
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(ktx.nth_texture(6)?)),
+        LOGO_LEVEL_6_BLAKE
+    );
+
+    let ktx = ktx::Decoder::new(BufReader::new(File::open("tests/babg-bc3.ktx")?))?;
+    assert_eq!(
+        ktx.nth_texture(8).unwrap_err().kind(),
+        io::ErrorKind::InvalidInput
+    );
+    Ok(())
+}
+
+#[test]
+fn read_logo_example_truncated() -> io::Result<()> {
+    let data = include_bytes!("babg-bc3.ktx");
+    // truncated within level 0 data & level 1 data
+    let level_1_start = 64 + 4 + 52000;
+    for end in [1000, level_1_start + 4 + 100] {
+        let truncated = &data[..end];
+        let err = ktx::Decoder::new(truncated)?.nth_texture(1).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    }
+
+    let err = ktx::Decoder::new(&data[..1000])?
+        .nth_texture(0)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+
+    // partial levels are not yielded
+    let truncated = &data[..level_1_start + 4 + 100];
+    assert_eq!(ktx::Decoder::new(truncated)?.read_textures().count(), 1);
+
+    // huge imageSize does not preallocate
+    let mut huge = data[..level_1_start].to_vec();
+    huge[64..68].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = ktx::Decoder::new(huge.as_slice())?
+        .nth_texture(0)
+        .unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
 #[test]
 fn logo_example_texture_level() {
     let ktx = include_ktx!("babg-bc3.ktx");
@@ -198,6 +344,16 @@ fn uffizi_6face_include() {
     assert_eq!(ktx.bytes_of_key_value_data(), 0, "bytes_of_key_value_data");
 
     assert_eq!(ktx.textures().count(), 10, "ktx.textures().count()");
+
+    assert!(!ktx.is_compressed(), "!is_compressed");
+    assert_eq!(ktx.block_dimensions(), (1, 1), "block_dimensions");
+    assert_eq!(
+        ktx.bytes_per_block_or_pixel(),
+        Some(8),
+        "bytes_per_block_or_pixel"
+    );
+    assert!(ktx.is_cubemap(), "is_cubemap");
+    assert!(!ktx.is_array(), "!is_array");
 }
 
 #[test]
@@ -229,3 +385,817 @@ fn uffizi_6face_read() {
         "ktx.read_textures().count()"
     );
 }
+
+#[test]
+fn uffizi_6face_texture_level() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+
+    let levels: Vec<_> = ktx.textures().collect();
+    for (n, level) in levels.into_iter().enumerate() {
+        assert_eq!(ktx.texture_level(n as _), level, "texture_level({n})");
+    }
+    assert_eq!(ktx.texture_level(9).len(), 6 * 8, "texture_level(9).len()");
+}
+
+#[test]
+fn logo_example_zero_mipmap_levels() {
+    // mipmap_levels 0 indicates only level 0 is stored
+    let mut data = include_bytes!("babg-bc3.ktx").to_vec();
+    data[56..60].copy_from_slice(&0_u32.to_le_bytes());
+
+    let ktx = Ktx::new(data.as_slice());
+    assert_eq!(ktx.mipmap_levels(), 0, "mipmap_levels");
+    let mut textures = ktx.textures();
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(textures.next().unwrap())),
+        LOGO_LEVEL_0_BLAKE
+    );
+    assert_eq!(textures.next(), None);
+
+    let decoder = ktx::Decoder::new(data.as_slice()).unwrap();
+    assert_eq!(
+        decoder.read_textures().count(),
+        1,
+        "read_textures().count()"
+    );
+}
+
+#[test]
+fn logo_example_truncated_try_textures() {
+    let data = include_bytes!("babg-bc3.ktx");
+    let level_0_end = 64 + 4 + include_ktx!("babg-bc3.ktx").texture_level(0).len();
+
+    // truncated within level 1 imageSize
+    let ktx = Ktx::new(&data[..level_0_end + 2]);
+    let mut textures = ktx.try_textures();
+    assert_eq!(
+        format!(
+            "{:x}",
+            Blake2s256::digest(textures.next().unwrap().unwrap())
+        ),
+        LOGO_LEVEL_0_BLAKE
+    );
+    assert_eq!(
+        textures.next(),
+        Some(Err(KtxError::TruncatedLevel { level: 1 }))
+    );
+    assert_eq!(textures.next(), None);
+
+    // complete data has no errors
+    let ktx = include_ktx!("babg-bc3.ktx");
+    assert_eq!(ktx.try_textures().filter(|l| l.is_ok()).count(), 8);
+}
+
+#[test]
+fn try_new_untrusted() {
+    let data = include_bytes!("babg-bc3.ktx");
+    assert_eq!(
+        Ktx::try_new(&data[..63]).unwrap_err(),
+        HeaderError::TooShort
+    );
+    assert_eq!(Ktx::try_new(&[]).unwrap_err(), HeaderError::TooShort);
+
+    let mut bad_identifier = data.to_vec();
+    bad_identifier[1] = b'X';
+    assert_eq!(
+        Ktx::try_new(bad_identifier).unwrap_err(),
+        HeaderError::InvalidIdentifier
+    );
+
+    // header only
+    let ktx = Ktx::try_new(&data[..64]).unwrap();
+    assert_eq!(
+        ktx.try_textures().next(),
+        Some(Err(KtxError::TruncatedLevel { level: 0 }))
+    );
+}
+
+#[test]
+fn try_textures_too_many_levels() {
+    let builder = (0..33).fold(
+        KtxBuilder::new()
+            .gl_type(format::UNSIGNED_BYTE)
+            .gl_format(format::RED)
+            .gl_internal_format(format::R8)
+            .gl_base_internal_format(format::RED)
+            .pixel_width(1)
+            .pixel_height(1)
+            .mipmap_levels(33),
+        |builder, _| builder.level(vec![0; 4]),
+    );
+    let ktx = Ktx::try_new(builder.to_vec().unwrap()).unwrap();
+
+    let mut textures = ktx.try_textures();
+    assert_eq!(textures.by_ref().take(32).filter(|l| l.is_ok()).count(), 32);
+    assert_eq!(
+        textures.next(),
+        Some(Err(KtxError::TooManyLevels { levels: 33 }))
+    );
+    assert_eq!(textures.next(), None);
+}
+
+#[test]
+fn uffizi_6face_read_faces() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let ktx_file = BufReader::new(File::open("tests/uffizi_rgba16f_cube.ktx").unwrap());
+    let faces: Vec<_> = ktx::Decoder::new(ktx_file).unwrap().read_faces().collect();
+
+    assert_eq!(faces.len(), 10 * 6, "faces.len()");
+    for (n, face) in faces.into_iter().enumerate() {
+        let (level, element_index, face_index, data) = face.unwrap();
+        assert_eq!(level, n as u32 / 6, "level");
+        assert_eq!(element_index, 0, "element_index");
+        assert_eq!(face_index, n as u32 % 6, "face_index");
+
+        let level_data = ktx.texture_level(level);
+        let face_len = level_data.len() / 6;
+        let face_start = face_len * face_index as usize;
+        assert_eq!(data, &level_data[face_start..face_start + face_len]);
+    }
+}
+
+#[test]
+fn read_logo_example_faces() {
+    let ktx_file = BufReader::new(File::open("tests/babg-bc3.ktx").unwrap());
+    let mut faces = ktx::Decoder::new(ktx_file).unwrap().read_faces();
+
+    let (level, element_index, face_index, data) = faces.next().unwrap().unwrap();
+    assert_eq!((level, element_index, face_index), (0, 0, 0));
+    assert_eq!(
+        format!("{:x}", Blake2s256::digest(data)),
+        LOGO_LEVEL_0_BLAKE
+    );
+    assert_eq!(faces.count(), 7);
+}
+
+#[test]
+fn read_faces_errors() {
+    let data = include_bytes!("uffizi_rgba16f_cube.ktx");
+    let mut faces = Decoder::new(&data[..data.len() - 10]).unwrap().read_faces();
+    let err = faces.find_map(Result::err).unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert!(faces.next().is_none());
+
+    // 2 element cube array with a level that doesn't split into 12 faces
+    let mut data = KtxBuilder::new()
+        .faces(6)
+        .array_elements(2)
+        .level(vec![0; 12 * 4])
+        .to_vec()
+        .unwrap();
+    data[64..68].copy_from_slice(&(12 * 4 - 1_u32).to_le_bytes());
+    data.truncate(data.len() - 1);
+    let mut faces = Decoder::new(data.as_slice()).unwrap().read_faces();
+    let err = faces.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(
+        err.to_string(),
+        KtxError::UnevenLevel { level: 0 }.to_string()
+    );
+    assert!(faces.next().is_none());
+
+    let data = KtxBuilder::new()
+        .faces(6)
+        .array_elements(2)
+        .level((0..12).flat_map(|n| [n; 4]).collect::<Vec<_>>())
+        .to_vec()
+        .unwrap();
+    let faces: Vec<_> = Decoder::new(data.as_slice())
+        .unwrap()
+        .read_faces()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(faces.len(), 12);
+    for (n, (level, element_index, face_index, face)) in faces.into_iter().enumerate() {
+        assert_eq!(
+            (level, element_index, face_index),
+            (0, n as u32 / 6, n as u32 % 6)
+        );
+        assert_eq!(face, [n as u8; 4]);
+    }
+}
+
+#[test]
+fn parse_orientation() {
+    use key_value::{Direction::*, Orientation};
+
+    assert_eq!(
+        "S=l,T=d,R=o".parse(),
+        Ok(Orientation {
+            s: Left,
+            t: Down,
+            r: Some(Out)
+        })
+    );
+    assert_eq!(Orientation::from_value(b"S=r,T=d\0").unwrap().t, Down);
+    assert!("S=r".parse::<Orientation>().is_err());
+    assert!("S=u,T=d".parse::<Orientation>().is_err());
+    assert!("S=r,T=d,R=i,X".parse::<Orientation>().is_err());
+    assert_eq!(include_ktx!("babg-bc3.ktx").orientation(), None);
+}
+
+#[test]
+fn header_from_prefix() {
+    let data = include_bytes!("uffizi_rgba16f_cube.ktx");
+    let header = header::KtxHeader::try_from_prefix(&data[..100]).unwrap();
+    assert_eq!(header, Decoder::new(&data[..]).unwrap().header());
+
+    let mut bad = data[..64].to_vec();
+    bad[12] = 9;
+    assert_eq!(
+        header::KtxHeader::try_from_prefix(&bad),
+        Err(HeaderError::InvalidEndianness)
+    );
+    bad[0] = 0;
+    assert_eq!(
+        header::KtxHeader::try_from_prefix(&bad),
+        Err(HeaderError::InvalidIdentifier)
+    );
+}
+
+#[test]
+fn cow_and_array_logo_example() {
+    let data = include_bytes!("babg-bc3.ktx");
+    let cow = Ktx::new(std::borrow::Cow::Borrowed(&data[..]));
+    let array = Ktx::new(data);
+    assert!(cow.textures().eq(array.textures()));
+    assert_eq!(cow.textures().len(), cow.mipmap_levels() as usize);
+}
+
+#[test]
+fn array_elements() {
+    // 2x2 RGBA8 3 element array, element n filled with n
+    let data = KtxBuilder::new()
+        .gl_type(format::UNSIGNED_BYTE)
+        .gl_format(format::RGBA)
+        .gl_internal_format(format::RGBA8)
+        .gl_base_internal_format(format::RGBA)
+        .pixel_width(2)
+        .pixel_height(2)
+        .array_elements(3)
+        .mipmap_levels(2)
+        .level([[0; 16], [1; 16], [2; 16]].concat())
+        .level([[0; 4], [1; 4], [2; 4]].concat())
+        .to_vec()
+        .unwrap();
+    let ktx = Ktx::new(data.as_slice());
+
+    let level_1: Vec<_> = ktx.elements(1).unwrap().collect();
+    assert_eq!(
+        level_1,
+        [(0, &[0; 4][..]), (1, &[1; 4][..]), (2, &[2; 4][..])]
+    );
+
+    let read: Vec<_> = Decoder::new(data.as_slice())
+        .unwrap()
+        .read_elements()
+        .collect();
+    assert_eq!(read.len(), 6);
+    for element in read {
+        let (level, element_index, element) = element.unwrap();
+        assert_eq!(
+            element,
+            ktx.elements(level)
+                .unwrap()
+                .nth(element_index as _)
+                .unwrap()
+                .1
+        );
+        assert!(element.iter().all(|b| *b == element_index as u8));
+    }
+    assert_eq!(include_ktx!("babg-bc3.ktx").elements(0).unwrap().len(), 1);
+}
+
+#[test]
+fn uneven_level_elements_faces() {
+    // 3 element array with a level that doesn't split into 3 elements
+    let mut data = KtxBuilder::new()
+        .array_elements(3)
+        .level(vec![0; 12])
+        .to_vec()
+        .unwrap();
+    data[64..68].copy_from_slice(&8_u32.to_le_bytes());
+    data.truncate(data.len() - 4);
+    let mut ktx = Ktx::new(data.clone());
+    let uneven = KtxError::UnevenLevel { level: 0 };
+    assert_eq!(ktx.elements(0).unwrap_err(), uneven);
+    assert_eq!(ktx.faces_mut(0).unwrap_err(), uneven);
+
+    let mut elements = Decoder::new(data.as_slice()).unwrap().read_elements();
+    let err = elements.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), uneven.to_string());
+    assert!(elements.next().is_none());
+
+    // empty levels yield empty elements & faces
+    let data = KtxBuilder::new()
+        .array_elements(3)
+        .level(vec![])
+        .to_vec()
+        .unwrap();
+    let mut ktx = Ktx::new(data);
+    assert!(ktx.elements(0).unwrap().eq((0..3).map(|n| (n, &[][..]))));
+    assert_eq!(ktx.faces_mut(0).unwrap().len(), 3);
+}
+
+#[test]
+fn read_logo_example_strict() {
+    use ktx::read::KtxReadError;
+
+    let data = include_bytes!("babg-bc3.ktx");
+    let strict = |data: &[u8]| -> Result<Vec<Vec<u8>>, KtxReadError> {
+        Decoder::new(data).unwrap().read_textures_strict().collect()
+    };
+
+    let levels = strict(data).unwrap();
+    assert!(levels
+        .iter()
+        .map(Vec::as_slice)
+        .eq(Ktx::new(data).textures()));
+
+    assert!(strict(include_bytes!("uffizi_rgba16f_cube.ktx")).is_ok());
+
+    let trailing = [&data[..], &[0; 5]].concat();
+    assert!(matches!(
+        strict(&trailing),
+        Err(KtxReadError::TrailingData { bytes: 5 })
+    ));
+
+    assert!(matches!(
+        strict(&data[..1000]),
+        Err(KtxReadError::UnexpectedEof { level: 0 })
+    ));
+
+    let mut bad_size = data.to_vec();
+    bad_size[64] += 16;
+    assert!(matches!(
+        strict(&bad_size),
+        Err(KtxReadError::ImageSize { level: 0, .. })
+    ));
+
+    // unknown format with an unchecked imageSize far beyond the data
+    let mut huge = KtxBuilder::new().level([0; 4]).to_vec().unwrap();
+    huge[64..68].copy_from_slice(&u32::MAX.to_le_bytes());
+    assert!(matches!(
+        strict(&huge),
+        Err(KtxReadError::UnexpectedEof { level: 0 })
+    ));
+
+    // 16384x16384 RGBA8 with a consistent 1 GiB imageSize, but no level data
+    let mut absent = ktx::from_rgba8(1, 1, &[0; 4]);
+    absent[36..44].copy_from_slice(&[16384_u32.to_le_bytes(), 16384_u32.to_le_bytes()].concat());
+    absent[64..68].copy_from_slice(&(1_u32 << 30).to_le_bytes());
+    absent.truncate(68);
+    assert!(matches!(
+        strict(&absent),
+        Err(KtxReadError::UnexpectedEof { level: 0 })
+    ));
+
+    // 1x1 RGBA8 with u32::MAX faces & array elements, a level length overflowing usize
+    let mut overflow = ktx::from_rgba8(1, 1, &[0; 4]);
+    overflow[48..56].fill(0xFF);
+    overflow[64..68].fill(0);
+    overflow.truncate(68);
+    assert!(matches!(
+        strict(&overflow),
+        Err(KtxReadError::DimensionsOverflow)
+    ));
+}
+
+#[test]
+fn uffizi_6face_big_endian_write_little_endian() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    assert_eq!(ktx.gl_type_size(), 2);
+
+    // convert to big endian, header values & half float texture data
+    let mut big = ktx.to_vec();
+    let swap = |data: &mut [u8], size| data.chunks_exact_mut(size).for_each(<[u8]>::reverse);
+    swap(&mut big[12..64], 4);
+    let mut offset = 64;
+    for level in ktx.textures() {
+        swap(&mut big[offset..offset + 4], 4);
+        swap(&mut big[offset + 4..offset + 4 + level.len()], 2);
+        offset += 4 + level.len();
+    }
+    let big = Ktx::new(big);
+    assert!(big.big_endian());
+    assert_eq!(big.pixel_width(), ktx.pixel_width());
+
+    let mut little = Vec::new();
+    big.write_little_endian_to(&mut little).unwrap();
+    assert_eq!(little, ktx.data());
+}
+
+#[test]
+fn write_little_endian_invalid_data() {
+    let data = include_bytes!("babg-bc3.ktx");
+    let write = |data: &[u8]| {
+        let mut out = Vec::new();
+        let result = Ktx::new(data).write_little_endian_to(&mut out);
+        (result, out)
+    };
+
+    // truncated level
+    let (result, out) = write(&data[..data.len() - 10]);
+    assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    assert!(out.is_empty());
+
+    // malformed key/value data
+    let with_key_value = KtxBuilder::new()
+        .gl_type(format::UNSIGNED_BYTE)
+        .gl_format(format::RED)
+        .gl_internal_format(format::R8)
+        .gl_base_internal_format(format::RED)
+        .pixel_width(4)
+        .pixel_height(1)
+        .level(vec![0; 4])
+        .key_value("KTXorientation", "S=r,T=d\0")
+        .to_vec()
+        .unwrap();
+    let mut malformed = with_key_value.clone();
+    // pair length beyond the key/value data
+    malformed[64] = 0xff;
+    let (result, _) = write(&malformed);
+    let err = result.unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert_eq!(err.to_string(), KtxError::InvalidKeyValues.to_string());
+
+    let (result, out) = write(&with_key_value);
+    result.unwrap();
+    assert_eq!(out, with_key_value);
+}
+
+#[test]
+fn texture_kinds() {
+    use header::TextureKind;
+
+    let kind = |height, depth, faces, elements| {
+        KtxBuilder::new()
+            .pixel_width(4)
+            .pixel_height(height)
+            .pixel_depth(depth)
+            .faces(faces)
+            .array_elements(elements)
+            .kind()
+    };
+    assert_eq!(kind(0, 0, 1, 0), TextureKind::D1);
+    assert_eq!(kind(0, 0, 1, 3), TextureKind::D1Array);
+    assert_eq!(kind(4, 0, 1, 0), TextureKind::D2);
+    assert_eq!(kind(4, 0, 1, 3), TextureKind::D2Array);
+    assert_eq!(kind(4, 4, 1, 0), TextureKind::D3);
+    assert_eq!(kind(4, 0, 6, 0), TextureKind::Cube);
+    assert_eq!(kind(4, 0, 6, 3), TextureKind::CubeArray);
+}
+
+#[test]
+fn uffizi_6face_sizes() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let level_sum: usize = ktx.textures().map(<[u8]>::len).sum();
+    assert_eq!(ktx.total_texture_bytes(), level_sum);
+    assert_eq!(ktx.estimated_gpu_size(4), Some(level_sum as u64));
+
+    // 2x3 RGB8, 6 byte rows
+    let rgb = Ktx::new(from_rgb8(2, 3, &[0; 2 * 3 * 3]));
+    assert_eq!(rgb.estimated_gpu_size(1), Some(18));
+    assert_eq!(rgb.estimated_gpu_size(8), Some(24));
+    assert_eq!(rgb.total_texture_bytes(), 24);
+
+    // u32::MAX faces & array elements
+    let mut overflow = from_rgba8(1, 1, &[0; 4]);
+    overflow[48..56].fill(0xFF);
+    assert_eq!(Ktx::new(overflow).estimated_gpu_size(4), None);
+}
+
+#[test]
+fn read_uffizi_6face_reversed() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let ktx_file = BufReader::new(File::open("tests/uffizi_rgba16f_cube.ktx").unwrap());
+    let reversed = Decoder::new(ktx_file)
+        .unwrap()
+        .read_textures_reversed()
+        .unwrap();
+    assert_eq!(reversed.len(), 10);
+
+    let mut expected_level = 10;
+    for (read, expected) in reversed.zip(ktx.textures().rev()) {
+        let (level, data) = read.unwrap();
+        expected_level -= 1;
+        assert_eq!(level, expected_level);
+        assert_eq!(data, expected);
+    }
+    assert_eq!(expected_level, 0);
+}
+
+#[test]
+fn read_logo_example_reversed_truncated() {
+    let data = include_bytes!("babg-bc3.ktx");
+    let mut reversed = Decoder::new(Cursor::new(&data[..data.len() - 10]))
+        .unwrap()
+        .read_textures_reversed()
+        .unwrap();
+    assert_eq!(reversed.len(), 8);
+    let err = reversed.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert_eq!(reversed.len(), 0);
+    assert!(reversed.next().is_none());
+}
+
+#[test]
+fn uffizi_6face_header_display() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let header: &header::KtxHeader = ktx.as_ref();
+    assert_eq!(header.to_string(), "cubemap 512x512 RGBA16F, 10 levels");
+    assert_eq!(
+        format!("{header:#}"),
+        "kind: cubemap\n\
+         dimensions: 512x512\n\
+         glType: HALF_FLOAT (0x140b)\n\
+         glFormat: RGBA (0x1908)\n\
+         glInternalFormat: RGBA16F (0x881a)\n\
+         glBaseInternalFormat: RGBA (0x1908)\n\
+         glTypeSize: 2\n\
+         arrayElements: 0\n\
+         faces: 6\n\
+         mipmapLevels: 10\n\
+         bytesOfKeyValueData: 0\n\
+         endianness: little"
+    );
+}
+
+#[test]
+fn uffizi_6face_faces_mut() {
+    let original = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let mut ktx = original.into_owned();
+
+    let faces: Vec<_> = ktx
+        .faces_mut(1)
+        .unwrap()
+        .map(|(index, data)| (index, data.len()))
+        .collect();
+    let face_len = original.texture_level(1).len() / 6;
+    assert_eq!(faces, (0..6).map(|f| (f, face_len)).collect::<Vec<_>>());
+
+    for (face_index, data) in ktx.faces_mut(1).unwrap() {
+        data.fill(face_index as u8);
+    }
+    let level = ktx.texture_level(1);
+    for (face_index, face) in level.chunks_exact(face_len).enumerate() {
+        assert!(face.iter().all(|b| *b == face_index as u8));
+    }
+
+    // other levels are unmodified
+    assert_eq!(ktx.texture_level(0), original.texture_level(0));
+    assert_eq!(ktx.texture_level(2), original.texture_level(2));
+
+    ktx.texture_level_mut(1)
+        .copy_from_slice(original.texture_level(1));
+    assert_eq!(ktx.data(), original.data());
+}
+
+#[test]
+fn header_bytes_round_trip() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let header: header::KtxHeader = *ktx.as_ref();
+    let bytes = header.to_bytes();
+    assert_eq!(&bytes[..], &ktx.data()[..64]);
+    assert_eq!(header::KtxHeader::try_from_bytes(&bytes), Ok(header));
+
+    let mut bad_endianness = bytes;
+    bad_endianness[12] = 9;
+    assert_eq!(
+        header::KtxHeader::try_from_bytes(&bad_endianness),
+        Err(HeaderError::InvalidEndianness)
+    );
+
+    let err = Decoder::new(&[0; 64][..]).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn read_uffizi_6face_level_chunks() -> io::Result<()> {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+
+    // small buffer so levels span many chunks
+    let reader = BufReader::with_capacity(1000, File::open("tests/uffizi_rgba16f_cube.ktx")?);
+    let decoder = ktx::Decoder::new(reader)?;
+    let mut levels: Vec<Vec<u8>> = Vec::new();
+    decoder.for_each_level_chunk(|level, chunk| {
+        assert!(chunk.len() <= 1000);
+        if levels.len() <= level as usize {
+            levels.push(Vec::new());
+        }
+        levels[level as usize].extend_from_slice(chunk);
+    })?;
+    assert_eq!(levels, ktx.textures().collect::<Vec<_>>());
+
+    let truncated = ktx::Decoder::new(&ktx.data()[..1000])?;
+    let err = truncated.for_each_level_chunk(|_, _| {}).unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
+#[test]
+fn read_textures_restartable() -> io::Result<()> {
+    let ktx_data = KtxBuilder::new()
+        .gl_type(format::UNSIGNED_BYTE)
+        .gl_type_size(1)
+        .gl_format(format::RED)
+        .gl_internal_format(format::R8)
+        .gl_base_internal_format(format::RED)
+        .pixel_width(4)
+        .pixel_height(2)
+        .mipmap_levels(2)
+        .level(vec![1; 8])
+        .level(vec![2; 4])
+        .key_value("KTXorientation", "S=r,T=d\0")
+        .to_vec()
+        .unwrap();
+
+    // ktx data not at the start of the reader
+    let mut data = vec![0xff; 3];
+    data.extend_from_slice(&ktx_data);
+    let mut reader = io::Cursor::new(data);
+    io::Seek::seek(&mut reader, io::SeekFrom::Start(3))?;
+
+    let mut decoder = ktx::Decoder::new(reader)?;
+    let expected = vec![vec![1; 8], vec![2; 4]];
+    assert_eq!(
+        decoder.textures()?.collect::<io::Result<Vec<_>>>()?,
+        expected
+    );
+    assert_eq!(
+        decoder.textures()?.collect::<io::Result<Vec<_>>>()?,
+        expected
+    );
+
+    // restart after partial iteration
+    assert_eq!(decoder.textures()?.next().transpose()?, Some(vec![1; 8]));
+    assert_eq!(
+        decoder.textures()?.collect::<io::Result<Vec<_>>>()?,
+        expected
+    );
+
+    // truncated level 1 is reported, then iteration ends
+    let truncated = ktx_data[..ktx_data.len() - 2].to_vec();
+    let mut decoder = ktx::Decoder::new(io::Cursor::new(truncated))?;
+    let mut textures = decoder.textures()?;
+    assert_eq!(textures.next().transpose()?, Some(vec![1; 8]));
+    let err = textures.next().unwrap().unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    assert!(textures.next().is_none());
+    Ok(())
+}
+
+#[test]
+fn sub_image_3d_array() {
+    // 2 layers of 2x2x2, then 1x1x1
+    let level_0: Vec<u8> = (0..32).collect();
+    let level_1: Vec<u8> = (100..108).collect();
+    let ktx_data = KtxBuilder::new()
+        .gl_type(format::UNSIGNED_BYTE)
+        .gl_type_size(1)
+        .gl_format(format::RED)
+        .gl_internal_format(format::R8)
+        .gl_base_internal_format(format::RED)
+        .pixel_width(2)
+        .pixel_height(2)
+        .pixel_depth(2)
+        .array_elements(2)
+        .mipmap_levels(2)
+        .level(level_0.clone())
+        .level(level_1.clone())
+        .to_vec()
+        .unwrap();
+    let ktx = Ktx::new(ktx_data.clone());
+
+    // each slice is 2 rows padded to 4 bytes
+    assert_eq!(ktx.sub_image(0, 0, 0, 0), &level_0[..8]);
+    assert_eq!(ktx.sub_image(0, 0, 0, 1), &level_0[8..16]);
+    assert_eq!(ktx.sub_image(0, 1, 0, 0), &level_0[16..24]);
+    assert_eq!(ktx.sub_image(0, 1, 0, 1), &level_0[24..]);
+    assert_eq!(ktx.sub_image(1, 1, 0, 0), &level_1[4..]);
+
+    assert_eq!(ktx.sub_image_location(0, 2, 0, 0), None);
+    assert_eq!(ktx.sub_image_location(0, 0, 1, 0), None);
+    assert_eq!(ktx.sub_image_location(1, 0, 0, 1), None);
+    assert_eq!(ktx.sub_image_location(2, 0, 0, 0), None);
+
+    // level 0 shorter than the header dimensions
+    let mut short = ktx_data;
+    short[64..68].copy_from_slice(&28_u32.to_le_bytes());
+    assert_eq!(Ktx::new(short).sub_image_location(0, 0, 0, 0), None);
+}
+
+#[test]
+fn uffizi_6face_sub_image() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    for level in 0..10 {
+        let faces: Vec<&[u8]> = ktx
+            .texture_level(level)
+            .chunks(ktx.texture_level(level).len() / 6)
+            .collect();
+        for (face, expected) in faces.into_iter().enumerate() {
+            assert_eq!(ktx.sub_image(level, 0, face as _, 0), expected);
+        }
+    }
+}
+
+#[test]
+fn read_textures_with_pool() -> io::Result<()> {
+    let ktx = include_ktx!("babg-bc3.ktx");
+    let mut pool: Vec<Vec<u8>> = (0..8).map(|_| Vec::with_capacity(60_000)).collect();
+    let pool_ptrs: Vec<*const u8> = pool.iter().map(|buf| buf.as_ptr()).collect();
+
+    let mut lens = Vec::new();
+    let decoder = ktx::Decoder::new(BufReader::new(File::open("tests/babg-bc3.ktx")?))?;
+    let levels: Vec<Vec<u8>> = decoder
+        .read_textures_with(|len| {
+            lens.push(len);
+            let mut buf = pool.pop().unwrap();
+            // stale data is cleared
+            buf.push(0xff);
+            buf
+        })
+        .collect::<Result<_, _>>()?;
+
+    assert_eq!(levels, ktx.textures().collect::<Vec<_>>());
+    assert_eq!(lens, levels.iter().map(Vec::len).collect::<Vec<_>>());
+    // buffers are reused without reallocating
+    for (level, ptr) in levels.iter().zip(pool_ptrs.iter().rev()) {
+        assert_eq!(level.as_ptr(), *ptr);
+    }
+
+    let data = include_bytes!("babg-bc3.ktx");
+    let mut truncated = Decoder::new(&data[..data.len() - 10])?.read_textures_with(|_| Vec::new());
+    assert_eq!(truncated.by_ref().filter(Result::is_ok).count(), 7);
+    assert!(truncated.next().is_none());
+    let err = Decoder::new(&data[..data.len() - 10])?
+        .read_textures_with(|_| Vec::new())
+        .find_map(Result::err)
+        .unwrap();
+    assert_eq!(err.kind(), io::ErrorKind::UnexpectedEof);
+    Ok(())
+}
+
+#[test]
+fn readers_skip_mip_padding() -> io::Result<()> {
+    // unknown format with 3 & 1 byte levels, each followed by mipPadding
+    let data = KtxBuilder::new()
+        .mipmap_levels(2)
+        .level([1, 2, 3])
+        .level([4])
+        .to_vec()
+        .unwrap();
+    let expected: Vec<_> = Ktx::new(data.as_slice())
+        .textures()
+        .map(<[u8]>::to_vec)
+        .collect();
+    assert_eq!(expected, [vec![1, 2, 3], vec![4]]);
+    let decoder = || Decoder::new(Cursor::new(data.as_slice()));
+
+    let read: Vec<_> = decoder()?.read_textures().collect();
+    assert_eq!(read, expected);
+    assert_eq!(decoder()?.nth_texture(1)?, expected[1]);
+    let read = decoder()?.textures()?.collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, expected);
+    let read = decoder()?
+        .read_textures_with(|_| Vec::new())
+        .collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, expected);
+    let read = decoder()?
+        .read_faces()
+        .map(|face| face.map(|(.., data)| data))
+        .collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, expected);
+    let read = decoder()?
+        .read_elements()
+        .map(|element| element.map(|(.., data)| data))
+        .collect::<io::Result<Vec<_>>>()?;
+    assert_eq!(read, expected);
+    let read: Vec<_> = decoder()?
+        .read_textures_strict()
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(read, expected);
+
+    let mut read = vec![vec![]; 2];
+    decoder()?
+        .for_each_level_chunk(|level, chunk| read[level as usize].extend_from_slice(chunk))?;
+    assert_eq!(read, expected);
+
+    let mut read = decoder()?
+        .read_textures_reversed()?
+        .map(|level| level.map(|(_, data)| data))
+        .collect::<io::Result<Vec<_>>>()?;
+    read.reverse();
+    assert_eq!(read, expected);
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "KTX mipmap_levels exceeds the maximum of 32")]
+fn from_static_too_many_levels() {
+    let mut data = include_bytes!("babg-bc3.ktx").to_vec();
+    data[56..60].copy_from_slice(&33_u32.to_le_bytes());
+    Ktx::from_static(Vec::leak(data));
+}
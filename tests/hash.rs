@@ -0,0 +1,40 @@
+#![cfg(feature = "hash")]
+use ktx::{format::*, key_value::KTX_HASH, *};
+
+#[test]
+fn content_hash_verify() {
+    let ktx_data = KtxBuilder::new()
+        .gl_type(UNSIGNED_BYTE)
+        .gl_type_size(1)
+        .gl_format(RGBA)
+        .gl_internal_format(RGBA8)
+        .gl_base_internal_format(RGBA)
+        .pixel_width(2)
+        .pixel_height(2)
+        .mipmap_levels(2)
+        .level(vec![7; 16])
+        .level(vec![9; 4])
+        .content_hash()
+        .to_vec()
+        .unwrap();
+
+    let ktx = Ktx::new(&ktx_data);
+    assert_eq!(ktx.verify_hash(), Some(true));
+    let value = ktx.key_value(KTX_HASH).unwrap();
+    assert_eq!(
+        value,
+        format!("blake3:{}\0", ktx.content_hash().to_hex()).as_bytes()
+    );
+
+    // modify level 1 data
+    let mut modified = ktx_data.clone();
+    *modified.last_mut().unwrap() = 0;
+    assert_eq!(Ktx::new(modified).verify_hash(), Some(false));
+
+    // truncated data
+    let truncated = &ktx_data[..ktx_data.len() - 8];
+    assert_eq!(Ktx::new(truncated).verify_hash(), Some(false));
+
+    // no hash metadata
+    assert_eq!(include_ktx!("babg-bc3.ktx").verify_hash(), None);
+}
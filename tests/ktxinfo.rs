@@ -0,0 +1,71 @@
+#![cfg(feature = "cli")]
+use std::process::{Command, Output};
+
+fn ktxinfo(path: impl AsRef<std::ffi::OsStr>) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_ktxinfo"))
+        .arg(path)
+        .output()
+        .unwrap()
+}
+
+#[test]
+fn ktxinfo_logo_example() {
+    let output = ktxinfo("tests/babg-bc3.ktx");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout
+        .starts_with("tests/babg-bc3.ktx: 2D 260x200 COMPRESSED_RGBA_S3TC_DXT5_EXT, 8 levels\n"));
+    assert!(stdout.contains("\n      0         68      52000  260x200\n"));
+    assert!(stdout.contains("\n      7      70000         16  2x1\n"));
+    assert!(stdout.ends_with("validation:\n  info: missing KTXorientation metadata\n"));
+}
+
+#[test]
+fn ktxinfo_cubemap() {
+    let output = ktxinfo("tests/uffizi_rgba16f_cube.ktx");
+    assert!(output.status.success());
+
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("faces: 6\n"));
+    assert!(stdout.contains("  512x512\n"));
+}
+
+#[test]
+fn ktxinfo_dimensions_overflow() {
+    // 1x1 RGBA8 with u32::MAX faces & array elements
+    let mut data = ktx::from_rgba8(1, 1, &[0; 4]);
+    data[48..56].fill(0xFF);
+    data[64..68].fill(0);
+    data.truncate(68);
+    let path = std::env::temp_dir().join("ktxinfo-dimensions-overflow.ktx");
+    std::fs::write(&path, data).unwrap();
+
+    let output = ktxinfo(&path);
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.ends_with("  warning: header dimensions overflow\n"));
+}
+
+#[test]
+fn ktxinfo_errors() {
+    let output = Command::new(env!("CARGO_BIN_EXE_ktxinfo"))
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+
+    // level 1 onwards missing
+    let path = std::env::temp_dir().join("ktxinfo-truncated.ktx");
+    std::fs::write(&path, &include_bytes!("babg-bc3.ktx")[..60_000]).unwrap();
+    let output = ktxinfo(&path);
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("  error: "));
+
+    // invalid header
+    std::fs::write(&path, b"not a ktx file").unwrap();
+    let output = ktxinfo(&path);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert!(!output.stderr.is_empty());
+}
@@ -0,0 +1,95 @@
+use ktx::{
+    format::*,
+    include_ktx, lint,
+    lint::{Diagnostic, Severity},
+    Ktx, KtxBuilder,
+};
+
+#[test]
+fn lint_cubemap() {
+    let image = include_ktx!("uffizi_rgba16f_cube.ktx");
+    assert_eq!(lint(&image), [Diagnostic::MissingOrientation]);
+
+    // level 0 imageSize written as the size of all 6 faces
+    let mut data = image.to_vec();
+    let level_len = image.texture_level(0).len() as u32;
+    data[64..68].copy_from_slice(&level_len.to_le_bytes());
+    let diagnostics = lint(&Ktx::new(data));
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic::MissingOrientation,
+            Diagnostic::CubemapLevelImageSize
+        ]
+    );
+    assert_eq!(diagnostics[1].severity(), Severity::Warning);
+}
+
+#[test]
+fn lint_npot_generated_compressed_mipmaps() {
+    let data = KtxBuilder::new()
+        .gl_internal_format(COMPRESSED_RGBA_S3TC_DXT5_EXT)
+        .gl_base_internal_format(RGBA)
+        .pixel_width(12)
+        .pixel_height(4)
+        .mipmap_levels(0)
+        .level(vec![0; 48])
+        .key_value("KTXorientation", "S=r,T=d\0")
+        .to_vec()
+        .unwrap();
+
+    let diagnostics = lint(&Ktx::new(data));
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic::NonPowerOfTwoMipmaps {
+                width: 12,
+                height: 4
+            },
+            Diagnostic::GeneratedCompressedMipmaps,
+        ]
+    );
+    assert!(diagnostics
+        .iter()
+        .all(|d| d.severity() == Severity::Warning));
+}
+
+#[test]
+fn lint_level_size() {
+    let mut data = include_bytes!("babg-bc3.ktx").to_vec();
+    // patch pixelWidth 260 -> 256, level data is now larger than expected
+    data[36..40].copy_from_slice(&256u32.to_le_bytes());
+
+    let diagnostics = lint(&Ktx::new(data));
+    assert_eq!(
+        diagnostics[1],
+        Diagnostic::LevelSize {
+            level: 0,
+            expected: 51200,
+            actual: 52000
+        }
+    );
+    assert_eq!(
+        diagnostics[1].to_string(),
+        "expected texture level 0 to be 51200 bytes, got 52000"
+    );
+}
+
+#[test]
+fn lint_dimensions_overflow() {
+    // 1x1 RGBA8 with u32::MAX faces & array elements, a level length overflowing usize
+    let mut data = ktx::from_rgba8(1, 1, &[0; 4]);
+    data[48..56].fill(0xFF);
+    data[64..68].fill(0);
+    data.truncate(68);
+
+    let diagnostics = lint(&Ktx::new(data));
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic::MissingOrientation,
+            Diagnostic::DimensionsOverflow
+        ]
+    );
+    assert_eq!(diagnostics[1].to_string(), "header dimensions overflow");
+}
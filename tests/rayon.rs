@@ -0,0 +1,39 @@
+#![cfg(feature = "rayon")]
+use ktx::*;
+use rayon::prelude::*;
+
+#[test]
+fn par_textures_uffizi_6face() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let levels: Vec<&[u8]> = ktx.par_textures().collect();
+    assert_eq!(levels, ktx.textures().collect::<Vec<_>>());
+}
+
+#[test]
+fn par_native_endian_textures_uffizi_6face_big_endian() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+
+    // little endian data is borrowed as-is on little endian hosts
+    let native: Vec<_> = ktx.par_native_endian_textures().collect();
+    if cfg!(target_endian = "little") {
+        assert!(native
+            .iter()
+            .all(|l| matches!(l, std::borrow::Cow::Borrowed(_))));
+    }
+
+    // convert to big endian, header values & half float texture data
+    let mut big = ktx.to_vec();
+    let swap = |data: &mut [u8], size| data.chunks_exact_mut(size).for_each(<[u8]>::reverse);
+    swap(&mut big[12..64], 4);
+    let mut offset = 64;
+    for level in ktx.textures() {
+        swap(&mut big[offset..offset + 4], 4);
+        swap(&mut big[offset + 4..offset + 4 + level.len()], 2);
+        offset += 4 + level.len();
+    }
+    let big = Ktx::new(big);
+    assert!(big.big_endian());
+
+    let big_native: Vec<_> = big.par_native_endian_textures().collect();
+    assert_eq!(big_native, native);
+}
@@ -0,0 +1,70 @@
+#![cfg(feature = "ash")]
+use ash::vk;
+use ktx::{vulkan, *};
+
+#[test]
+fn vulkan_uffizi_6face() {
+    let ktx = Ktx::new(&include_bytes!("uffizi_rgba16f_cube.ktx")[..]);
+    let descriptor = vulkan::image_descriptor(&ktx).unwrap();
+
+    let info = descriptor.create_info;
+    assert_eq!(info.format, vk::Format::R16G16B16A16_SFLOAT);
+    assert_eq!(info.image_type, vk::ImageType::TYPE_2D);
+    assert_eq!(info.flags, vk::ImageCreateFlags::CUBE_COMPATIBLE);
+    assert_eq!(info.array_layers, 6);
+    assert_eq!(info.mip_levels, ktx.mipmap_levels());
+    assert_eq!(
+        (info.extent.width, info.extent.height, info.extent.depth),
+        (512, 512, 1)
+    );
+
+    assert_eq!(descriptor.regions.len(), ktx.textures().len());
+    let mut expected_offset = 0;
+    for ((level, region), data) in descriptor.regions.iter().enumerate().zip(ktx.textures()) {
+        assert_eq!(region.buffer_offset, expected_offset);
+        assert_eq!(region.image_subresource.mip_level, level as u32);
+        assert_eq!(region.image_subresource.layer_count, 6);
+        assert_eq!(region.image_extent.width, 512 >> level);
+        expected_offset += data.len() as u64;
+    }
+    assert_eq!(descriptor.buffer_size, expected_offset);
+}
+
+#[test]
+fn vulkan_rgb8_row_padding() {
+    // 3 pixel rows are 9 bytes, padded to 12 i.e. 4 pixels
+    let data = from_rgb8(3, 2, &[0; 18]);
+    let descriptor = vulkan::image_descriptor(&Ktx::new(data)).unwrap();
+    assert_eq!(descriptor.create_info.format, vk::Format::R8G8B8_UNORM);
+    assert_eq!(descriptor.regions[0].buffer_row_length, 4);
+    assert_eq!(descriptor.buffer_size, 24);
+
+    // 1 pixel rows are 3 bytes, padded to 4
+    let data = from_rgb8(1, 1, &[0; 3]);
+    let err = vulkan::image_descriptor(&Ktx::new(data)).unwrap_err();
+    assert_eq!(err, vulkan::VulkanError::RowPadding);
+}
+
+#[test]
+fn vulkan_too_many_levels() {
+    let mut data = include_ktx!("babg-bc3.ktx").to_vec();
+    // 260x200 has a 9 level full chain
+    data[56..60].copy_from_slice(&u32::MAX.to_le_bytes());
+    let err = vulkan::image_descriptor(&Ktx::new(data)).unwrap_err();
+    assert_eq!(
+        err,
+        vulkan::VulkanError::TooManyLevels {
+            levels: u32::MAX,
+            max: 9
+        }
+    );
+}
+
+#[test]
+fn vulkan_dimensions_overflow() {
+    // 1x1 RGBA8 with u32::MAX faces & array elements
+    let mut data = from_rgba8(1, 1, &[0; 4]);
+    data[48..56].fill(0xFF);
+    let err = vulkan::image_descriptor(&Ktx::new(data)).unwrap_err();
+    assert_eq!(err, vulkan::VulkanError::DimensionsOverflow);
+}
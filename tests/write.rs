@@ -0,0 +1,503 @@
+use ktx::{format::*, write::BuildError, *};
+
+/// Returns a builder with the same header values as the input.
+fn builder_from(info: &impl KtxInfo) -> KtxBuilder {
+    KtxBuilder::new()
+        .gl_type(info.gl_type())
+        .gl_type_size(info.gl_type_size())
+        .gl_format(info.gl_format())
+        .gl_internal_format(info.gl_internal_format())
+        .gl_base_internal_format(info.gl_base_internal_format())
+        .pixel_width(info.pixel_width())
+        .pixel_height(info.pixel_height())
+        .pixel_depth(info.pixel_depth())
+        .array_elements(info.array_elements())
+        .faces(info.faces())
+        .mipmap_levels(info.mipmap_levels())
+}
+
+#[test]
+fn rebuild_logo_example() {
+    let ktx = include_ktx!("babg-bc3.ktx");
+    let builder = ktx
+        .textures()
+        .fold(builder_from(&ktx), |builder, level| builder.level(level));
+
+    assert_eq!(builder.to_vec().unwrap(), ktx.data());
+}
+
+#[test]
+fn rebuild_uffizi_6face() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let builder = ktx
+        .textures()
+        .fold(builder_from(&ktx), |builder, level| builder.level(level));
+
+    assert_eq!(builder.to_vec().unwrap(), ktx.data());
+}
+
+#[test]
+fn build_level_count_error() {
+    let ktx = include_ktx!("babg-bc3.ktx");
+    let builder = builder_from(&ktx).level(ktx.texture_level(0));
+
+    assert_eq!(
+        builder.to_vec(),
+        Err(BuildError::LevelCount {
+            expected: 8,
+            actual: 1
+        })
+    );
+}
+
+#[test]
+fn build_level_size_error() {
+    let builder = KtxBuilder::new()
+        .gl_type(UNSIGNED_BYTE)
+        .gl_format(RGB)
+        .gl_internal_format(RGB8)
+        .gl_base_internal_format(RGB)
+        .pixel_width(3)
+        .pixel_height(2)
+        .level(vec![0; 3 * 3 * 2]);
+
+    // rows of 9 bytes are padded to 12
+    assert_eq!(
+        builder.to_vec(),
+        Err(BuildError::LevelSize {
+            level: 0,
+            expected: 24,
+            actual: 18
+        })
+    );
+}
+
+#[cfg(feature = "image")]
+#[test]
+fn build_from_png_with_mipmaps() {
+    let path = std::env::temp_dir().join("ktx-build-from-png.png");
+    image::RgbaImage::from_pixel(5, 3, image::Rgba([10, 20, 30, 255]))
+        .save(&path)
+        .unwrap();
+
+    let options = write::ImageOptions::new().mipmaps(true);
+    let data = KtxBuilder::from_image_path(&path, options)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    let ktx = Ktx::new(data.as_slice());
+    assert_eq!(ktx.gl_internal_format(), SRGB8_ALPHA8);
+    assert_eq!((ktx.pixel_width(), ktx.pixel_height()), (5, 3));
+    assert_eq!(ktx.mipmap_levels(), 3);
+
+    let levels: Vec<_> = ktx.textures().collect();
+    assert_eq!(levels[0], [10, 20, 30, 255].repeat(5 * 3));
+    assert_eq!(levels[1], [10, 20, 30, 255].repeat(2));
+    assert_eq!(levels[2], [10, 20, 30, 255]);
+
+    let missing = std::env::temp_dir().join("ktx-build-from-missing.png");
+    assert!(matches!(
+        KtxBuilder::from_image_path(missing, options),
+        Err(write::FromImageError::Image(_))
+    ));
+}
+
+#[test]
+fn build_key_values() {
+    use key_value::Channel;
+
+    let orientation = key_value::Orientation {
+        s: key_value::Direction::Right,
+        t: key_value::Direction::Up,
+        r: None,
+    };
+    let ktx = include_ktx!("babg-bc3.ktx");
+    let data = ktx
+        .textures()
+        .fold(builder_from(&ktx), |builder, level| builder.level(level))
+        .key_value("MyKey", [1, 2, 3])
+        .orientation(orientation)
+        .swizzle([Channel::Red, Channel::Red, Channel::Red, Channel::One])
+        .to_vec()
+        .unwrap();
+
+    let rebuilt = Ktx::new(data.as_slice());
+    assert_eq!(rebuilt.bytes_of_key_value_data(), 16 + 28 + 20);
+    assert_eq!(
+        rebuilt.key_values().collect::<Vec<_>>(),
+        [
+            ("MyKey", &[1, 2, 3][..]),
+            (key_value::KTX_ORIENTATION, b"S=r,T=u\0"),
+            (key_value::KTX_SWIZZLE, b"rrr1\0"),
+        ]
+    );
+    assert_eq!(rebuilt.orientation(), Some(orientation));
+    assert_eq!(
+        rebuilt.swizzle(),
+        Some([Channel::Red, Channel::Red, Channel::Red, Channel::One])
+    );
+    assert!(rebuilt.textures().eq(ktx.textures()));
+}
+
+#[test]
+fn from_rgb8_row_padding() {
+    // 3x2 pixels, 9 byte rows padded to 12
+    let pixels: Vec<u8> = (0..3 * 2 * 3).collect();
+    let data = from_rgb8(3, 2, &pixels);
+    let ktx = Ktx::new(data.as_slice());
+
+    assert_eq!(ktx.gl_internal_format(), RGB8);
+    assert_eq!((ktx.pixel_width(), ktx.pixel_height()), (3, 2));
+    assert_eq!(
+        ktx.texture_level(0),
+        [&pixels[..9], &[0; 3], &pixels[9..], &[0; 3]].concat()
+    );
+
+    let r8 = from_r8(1, 1, &[7]);
+    assert_eq!(Ktx::new(r8.as_slice()).texture_level(0), [7, 0, 0, 0]);
+}
+
+#[test]
+fn generate_mipmaps_rgb8() {
+    // 3x2 RGB8, 9 byte rows padded to 12
+    let row = |v: u8| [&[v; 9][..], &[0; 3]].concat();
+    let builder = KtxBuilder::new()
+        .gl_type(UNSIGNED_BYTE)
+        .gl_format(RGB)
+        .gl_internal_format(RGB8)
+        .gl_base_internal_format(RGB)
+        .pixel_width(3)
+        .pixel_height(2)
+        .level([row(10), row(20)].concat());
+
+    let data = builder
+        .clone()
+        .generate_mipmaps(write::MipFilter::Box)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    let ktx = Ktx::new(data.as_slice());
+    assert_eq!(ktx.mipmap_levels(), 2);
+    assert_eq!(ktx.texture_level(1), [15, 15, 15, 0]);
+
+    let triangle = builder
+        .clone()
+        .generate_mipmaps(write::MipFilter::Triangle)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    assert_eq!(
+        Ktx::new(triangle.as_slice()).texture_level(1),
+        [15, 15, 15, 0]
+    );
+
+    assert_eq!(
+        builder
+            .gl_type(FLOAT)
+            .generate_mipmaps(write::MipFilter::Box)
+            .unwrap_err(),
+        BuildError::MipmapsUnsupported
+    );
+}
+
+#[test]
+fn generate_mipmaps_srgb_linear() {
+    // black & white pixels average to linear 0.5, sRGB ~188, not 128
+    let builder = KtxBuilder::new()
+        .gl_type(UNSIGNED_BYTE)
+        .gl_format(RGBA)
+        .gl_internal_format(SRGB8_ALPHA8)
+        .gl_base_internal_format(RGBA)
+        .pixel_width(2)
+        .pixel_height(1)
+        .level([[0, 0, 0, 0], [255, 255, 255, 255]].concat());
+
+    let data = builder
+        .clone()
+        .generate_mipmaps(write::MipFilter::Box)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    assert_eq!(
+        Ktx::new(data.as_slice()).texture_level(1),
+        [188, 188, 188, 128]
+    );
+
+    let linear = builder
+        .gl_internal_format(RGBA8)
+        .generate_mipmaps(write::MipFilter::Box)
+        .unwrap()
+        .to_vec()
+        .unwrap();
+    assert_eq!(
+        Ktx::new(linear.as_slice()).texture_level(1),
+        [128, 128, 128, 128]
+    );
+}
+
+#[test]
+fn build_cubemap_constraints() {
+    let cube = |width, height| {
+        KtxBuilder::new()
+            .gl_internal_format(RGBA8)
+            .gl_format(RGBA)
+            .gl_type(UNSIGNED_BYTE)
+            .pixel_width(width)
+            .pixel_height(height)
+            .faces(6)
+    };
+    assert!(cube(2, 2).level(vec![0; 2 * 2 * 4 * 6]).to_vec().is_ok());
+
+    assert_eq!(
+        cube(4, 2).level(vec![0; 4 * 2 * 4 * 6]).to_vec(),
+        Err(BuildError::NonSquareFaces {
+            width: 4,
+            height: 2,
+            depth: 0
+        })
+    );
+    assert_eq!(
+        cube(2, 2).faces(3).level(vec![0; 2 * 2 * 4 * 3]).to_vec(),
+        Err(BuildError::FaceCount { faces: 3 })
+    );
+    assert_eq!(
+        cube(2, 2)
+            .gl_internal_format(PALETTE8_RGBA8_OES)
+            .level(vec![0; 1024 + 4 * 6])
+            .to_vec(),
+        Err(BuildError::PalettedCubemap)
+    );
+
+    // unknown formats are still checked for equal faces
+    assert_eq!(
+        cube(2, 2).gl_internal_format(0).level(vec![0; 13]).to_vec(),
+        Err(BuildError::UnequalFaces { level: 0 })
+    );
+}
+
+#[test]
+fn encode_uffizi_6face_per_face() -> std::io::Result<()> {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let mut encoder = builder_from(&ktx).encoder(Vec::new())?;
+    for level in ktx.textures() {
+        encoder.begin_level(level.len())?;
+        for face in level.chunks(level.len() / 6) {
+            encoder.write_face(face)?;
+        }
+        encoder.end_level()?;
+    }
+    assert_eq!(encoder.finish()?, ktx.data());
+    Ok(())
+}
+
+#[test]
+fn encode_logo_levels() -> std::io::Result<()> {
+    let ktx = include_ktx!("babg-bc3.ktx");
+    // builder levels are written first
+    let mut encoder = builder_from(&ktx)
+        .level(ktx.texture_level(0))
+        .encoder(Vec::new())?;
+    for level in ktx.textures().skip(1) {
+        encoder.write_level(level)?;
+    }
+    assert_eq!(encoder.finish()?, ktx.data());
+    Ok(())
+}
+
+#[test]
+fn encode_cube_read_back() -> std::io::Result<()> {
+    // unknown format, 8 byte faces
+    let mut encoder = KtxBuilder::new()
+        .faces(6)
+        .mipmap_levels(2)
+        .encoder(Vec::new())?;
+    for face_len in [8, 4] {
+        encoder.begin_level(6 * face_len)?;
+        for face in 0..6 {
+            encoder.write_face(&vec![face; face_len])?;
+        }
+        encoder.end_level()?;
+    }
+    let data = encoder.finish()?;
+    assert_eq!(&data[64..68], &8u32.to_le_bytes());
+
+    let ktx = Ktx::new(data.as_slice());
+    assert_eq!(ktx.textures().len(), 2);
+    for (level, face_len) in [(0, 8), (1, 4)] {
+        for face in 0..6 {
+            assert_eq!(ktx.sub_image(level, 0, face, 0), vec![face as u8; face_len]);
+        }
+    }
+    let read: Vec<_> = ktx::Decoder::new(data.as_slice())?
+        .read_textures()
+        .collect();
+    assert!(read.iter().map(Vec::as_slice).eq(ktx.textures()));
+    Ok(())
+}
+
+#[test]
+fn encode_cube_unaligned_faces() -> std::io::Result<()> {
+    // unknown format, 3 byte faces would require cubePadding
+    let mut encoder = KtxBuilder::new().faces(6).encoder(Vec::new())?;
+    let err = encoder.begin_level(6 * 3).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        BuildError::UnalignedFaces { level: 0 }.to_string()
+    );
+
+    assert_eq!(
+        KtxBuilder::new().faces(6).level(vec![0; 6 * 3]).to_vec(),
+        Err(BuildError::UnalignedFaces { level: 0 })
+    );
+    Ok(())
+}
+
+#[test]
+fn encoder_validates_faces() {
+    let err = KtxBuilder::new().faces(3).encoder(Vec::new()).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        BuildError::FaceCount { faces: 3 }.to_string()
+    );
+
+    let err = KtxBuilder::new()
+        .faces(6)
+        .pixel_width(4)
+        .pixel_height(2)
+        .encoder(Vec::new())
+        .unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+}
+
+#[test]
+fn encode_empty_level() -> std::io::Result<()> {
+    // unknown format, so zero length levels are accepted
+    for builder in [KtxBuilder::new(), KtxBuilder::new().faces(6)] {
+        let built = builder.clone().level(vec![]).to_vec().unwrap();
+
+        let mut encoder = builder.encoder(Vec::new())?;
+        encoder.write_level(&[])?;
+        assert_eq!(encoder.finish()?, built);
+    }
+    Ok(())
+}
+
+#[test]
+fn encode_level_too_large() -> std::io::Result<()> {
+    // unknown format, so only imageSize limits the length
+    let mut encoder = KtxBuilder::new().encoder(Vec::new())?;
+    let err = encoder.begin_level(1 << 32).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        BuildError::LevelTooLarge { level: 0 }.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn encode_errors() -> std::io::Result<()> {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let level_len = ktx.texture_level(0).len();
+    let mut encoder = builder_from(&ktx).encoder(Vec::new())?;
+
+    let err = encoder.begin_level(level_len - 6).unwrap_err();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidInput);
+    assert_eq!(
+        err.to_string(),
+        format!(
+            "expected texture level 0 to be {level_len} bytes, got {}",
+            level_len - 6
+        )
+    );
+    assert!(encoder.write_face(&[0; 4]).is_err());
+
+    encoder.begin_level(level_len)?;
+    assert!(encoder.begin_level(level_len).is_err());
+    assert!(encoder.write_face(&[0; 4]).is_err());
+    encoder.write_face(&ktx.texture_level(0)[..level_len / 6])?;
+    assert!(encoder.end_level().is_err());
+
+    let err = KtxBuilder::new().encoder(Vec::new())?.finish().unwrap_err();
+    assert_eq!(err.to_string(), "expected 1 texture levels, got 0");
+    Ok(())
+}
+
+#[test]
+fn build_cube_levels() {
+    let ktx = include_ktx!("uffizi_rgba16f_cube.ktx");
+    let builder = ktx
+        .textures()
+        .fold(builder_from(&ktx).faces(1), |builder, level| {
+            let faces: Vec<&[u8]> = level.chunks(level.len() / 6).collect();
+            builder
+                .add_cube_level([faces[0], faces[1], faces[2], faces[3], faces[4], faces[5]])
+                .unwrap()
+        });
+    assert_eq!(builder.to_vec().unwrap(), ktx.data());
+
+    let cube = |width, height| {
+        KtxBuilder::new()
+            .gl_internal_format(RGBA8)
+            .gl_format(RGBA)
+            .gl_type(UNSIGNED_BYTE)
+            .pixel_width(width)
+            .pixel_height(height)
+    };
+    let faces = [
+        vec![0; 32],
+        vec![0; 32],
+        vec![0; 32],
+        vec![0; 32],
+        vec![0; 32],
+        vec![0; 32],
+    ];
+    assert_eq!(
+        cube(4, 2).add_cube_level(faces.clone()).unwrap_err(),
+        BuildError::NonSquareFaces {
+            width: 4,
+            height: 2,
+            depth: 0
+        }
+    );
+
+    let mut unequal = faces;
+    unequal[4] = vec![0; 16];
+    assert_eq!(
+        cube(2, 2).add_cube_level(unequal).unwrap_err(),
+        BuildError::UnequalFaces { level: 0 }
+    );
+}
+
+#[test]
+fn read_cube_padding() -> std::io::Result<()> {
+    // 3 byte faces each followed by 1 byte of cubePadding, as written by other encoders
+    let mut data = KtxBuilder::new()
+        .faces(6)
+        .level(vec![0; 6 * 4])
+        .to_vec()
+        .unwrap();
+    data[64..68].copy_from_slice(&3u32.to_le_bytes());
+    for face in 0..6 {
+        data[68 + face * 4..][..3].copy_from_slice(&[face as u8; 3]);
+    }
+
+    let ktx = Ktx::new(data.as_slice());
+    assert_eq!(ktx.texture_level(0).len(), 6 * 4);
+    for face in 0..6 {
+        assert_eq!(ktx.sub_image(0, 0, face, 0), [face as u8; 3]);
+    }
+    let read: Vec<_> = ktx::Decoder::new(data.as_slice())?
+        .read_textures()
+        .collect();
+    assert!(read.iter().map(Vec::as_slice).eq(ktx.textures()));
+    Ok(())
+}
+
+#[test]
+#[should_panic(expected = "width & height must be non-zero")]
+fn from_rgba8_zero_width() {
+    ktx::from_rgba8(0, 2, &[]);
+}